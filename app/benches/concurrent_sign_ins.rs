@@ -0,0 +1,81 @@
+// Compares the throughput of sign-ins going through the blocking thread pool, the way the
+// handlers use `web::block`, with the async ones awaiting the queries directly.
+// Run with `cargo bench -p app`, it uses the test database and cleans it up afterwards.
+use std::future::Future;
+use std::time::Instant;
+
+use futures::future::join_all;
+
+use app::services::teachers::sessions::{sign_in, sign_in_async};
+use app::utils::password;
+use db::models::SessionLifetimes;
+use db::prelude::*;
+use db::utils::test::{db_cleanup, GLOBAL_SETUP};
+
+const SIGN_INS: usize = 512;
+const CONCURRENCY_LEVELS: [usize; 4] = [1, 8, 32, 128];
+const EMAIL: &str = "john.doe@example.com";
+const PASSWORD: &str = "password";
+
+// Runs the sign-ins in batches of `concurrency` and returns the number of sign-ins per second
+async fn measure<F, Fut>(concurrency: usize, sign_in: F) -> f64
+where
+  F: Fn() -> Fut,
+  Fut: Future<Output = ()>,
+{
+  let started_at = Instant::now();
+  for _ in 0..SIGN_INS / concurrency {
+    join_all((0..concurrency).map(|_| sign_in())).await;
+  }
+
+  SIGN_INS as f64 / started_at.elapsed().as_secs_f64()
+}
+
+fn main() {
+  GLOBAL_SETUP.unwrap();
+  let database_url = std::env::var("TEST_DATABASE_URL").unwrap();
+  let lifetimes = SessionLifetimes::default();
+  let pool_settings = PoolSettings::default();
+  let db_pool =
+    create_database_connection_pool(&database_url, &pool_settings, &PoolMetrics::default())
+      .unwrap();
+  TeachersRepository::new(&db_pool.get().unwrap())
+    .create(EMAIL.into(), password::digest(PASSWORD).unwrap())
+    .unwrap();
+
+  let mut runtime = tokio::runtime::Builder::new()
+    .basic_scheduler()
+    .enable_all()
+    .build()
+    .unwrap();
+
+  runtime.block_on(async {
+    let async_db_pool =
+      create_async_database_connection_pool(&database_url, &pool_settings).unwrap();
+
+    println!("{:>12} {:>16} {:>16}", "concurrency", "blocking (rps)", "async (rps)");
+
+    for &concurrency in CONCURRENCY_LEVELS.iter() {
+      let blocking = measure(concurrency, || {
+        let db_pool = db_pool.clone();
+        async move {
+          actix_threadpool::run(move || {
+            sign_in(EMAIL.into(), PASSWORD.into(), &lifetimes, &db_pool.get().unwrap())
+          }).await.unwrap();
+        }
+      }).await;
+
+      let non_blocking = measure(concurrency, || {
+        let async_db_pool = async_db_pool.clone();
+        async move {
+          let mut db = async_db_pool.get().await.unwrap();
+          sign_in_async(EMAIL.into(), PASSWORD.into(), &lifetimes, &mut db).await.unwrap();
+        }
+      }).await;
+
+      println!("{:>12} {:>16.1} {:>16.1}", concurrency, blocking, non_blocking);
+    }
+  });
+
+  db_cleanup();
+}
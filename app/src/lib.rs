@@ -5,7 +5,7 @@ pub mod utils;
 pub mod services;
 
 pub mod prelude {
-  pub use crate::utils::constants::ROLLBAR_CLIENT;
+  pub use crate::utils::rollbar::{configure_rollbar, ROLLBAR_CLIENT};
   pub use crate::{handle_unexpected_err, report_unexpected_err};
 }
 
@@ -0,0 +1,105 @@
+use serde::{Serialize, Serializer};
+use db::prelude::*;
+use db::models::Country;
+
+use crate::{handle_unexpected_err, make_serializable};
+
+#[derive(PartialEq, Debug)]
+pub enum ValidationError {
+  CodeIsBlank,
+}
+
+make_serializable!(ValidationError {
+  CodeIsBlank => ("code", "blank", "Country code can't be blank"),
+});
+
+#[derive(PartialEq, Debug)]
+pub enum FindError {
+  InvalidParams(Vec<ValidationError>),
+  CountryNotFound,
+  UnexpectedError,
+}
+
+struct Find<'a> {
+  code: String,
+  countries_repository: CountriesRepository<'a>,
+}
+
+impl<'a> Find<'a> {
+  fn new(code: String, db: &'a DbConnection) -> Self {
+    Self {
+      countries_repository: CountriesRepository::new(db),
+      // Codes are stored uppercase, but the lookup shouldn't depend on how the client spelled it
+      code: code.trim().to_uppercase(),
+    }
+  }
+
+  fn validate_params(&self) -> Result<(), FindError> {
+    let mut errors = vec![];
+
+    if self.code.is_empty() {
+      errors.push(ValidationError::CodeIsBlank);
+    }
+
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(FindError::InvalidParams(errors))
+    }
+  }
+
+  fn get_country(&self) -> Result<Country, FindError> {
+    match self.countries_repository.find_by_alpha2_code(&self.code) {
+      Ok(country) => Ok(country),
+      Err(DbError::RecordNotFound) => Err(FindError::CountryNotFound),
+      Err(error) => handle_unexpected_err!(error, FindError::UnexpectedError),
+    }
+  }
+
+  fn call(self) -> Result<Country, FindError> {
+    self.validate_params()?;
+    let country = self.get_country()?;
+
+    Ok(country)
+  }
+}
+
+pub fn find(code: String, db: &DbConnection) -> Result<Country, FindError> {
+  Find::new(code, db).call()
+}
+
+#[cfg(test)]
+mod tests {
+  use db::seeds;
+  use db::utils::test::{with_db, TestSchema};
+  use super::*;
+
+  // The imports run in their own schemas, so that they don't wait on each other's countries
+  #[test]
+  fn find_works() {
+    let schema = TestSchema::new();
+    let db = schema.connection();
+    seeds::countries::import(&db).unwrap();
+
+    let result = find("pl".into(), &db);
+    assert!(result.is_ok());
+    assert_eq!(result.unwrap().name_pl, "Polska");
+  }
+
+  #[test]
+  fn find_fails_when_code_is_blank() {
+    with_db(|db| {
+      assert_eq!(
+        find(" ".into(), &db),
+        Err(FindError::InvalidParams(vec![ValidationError::CodeIsBlank])),
+      );
+    });
+  }
+
+  #[test]
+  fn find_fails_when_country_doesnt_exist() {
+    with_db(|db| {
+      assert_eq!(find("XX".into(), &db), Err(FindError::CountryNotFound));
+    });
+  }
+}
@@ -0,0 +1,114 @@
+use serde::{Serialize, Serializer};
+use db::prelude::*;
+use db::models::Country;
+use db::models::country::CONTINENTS;
+
+use crate::{handle_unexpected_err, make_serializable};
+
+#[derive(PartialEq, Debug)]
+pub enum ValidationError {
+  ContinentIsInvalid,
+}
+
+make_serializable!(ValidationError {
+  ContinentIsInvalid => ("continent", "invalid", "Continent is invalid"),
+});
+
+#[derive(PartialEq, Debug)]
+pub enum ListError {
+  InvalidParams(Vec<ValidationError>),
+  UnexpectedError,
+}
+
+struct List<'a> {
+  continent: Option<String>,
+  countries_repository: CountriesRepository<'a>,
+}
+
+impl<'a> List<'a> {
+  fn new(continent: Option<String>, db: &'a DbConnection) -> Self {
+    Self {
+      countries_repository: CountriesRepository::new(db),
+      continent,
+    }
+  }
+
+  fn validate_params(&self) -> Result<(), ListError> {
+    let mut errors = vec![];
+
+    if let Some(continent) = &self.continent {
+      if !CONTINENTS.contains(&continent.as_str()) {
+        errors.push(ValidationError::ContinentIsInvalid);
+      }
+    }
+
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(ListError::InvalidParams(errors))
+    }
+  }
+
+  fn get_countries(&self) -> Result<Vec<Country>, ListError> {
+    let result = match &self.continent {
+      Some(continent) => self.countries_repository.by_continent(continent),
+      None => self.countries_repository.all(),
+    };
+
+    match result {
+      Ok(countries) => Ok(countries),
+      Err(error) => handle_unexpected_err!(error, ListError::UnexpectedError),
+    }
+  }
+
+  fn call(self) -> Result<Vec<Country>, ListError> {
+    self.validate_params()?;
+    let countries = self.get_countries()?;
+
+    Ok(countries)
+  }
+}
+
+pub fn list(continent: Option<String>, db: &DbConnection) -> Result<Vec<Country>, ListError> {
+  List::new(continent, db).call()
+}
+
+#[cfg(test)]
+mod tests {
+  use db::seeds;
+  use db::utils::test::{with_db, TestSchema};
+  use super::*;
+
+  // The imports run in their own schemas, so that they don't wait on each other's countries
+  #[test]
+  fn list_works() {
+    let schema = TestSchema::new();
+    let db = schema.connection();
+    seeds::countries::import(&db).unwrap();
+
+    let result = list(None, &db);
+    assert!(result.is_ok());
+    assert_eq!(result.unwrap().len(), 196);
+  }
+
+  #[test]
+  fn list_works_with_continent() {
+    let schema = TestSchema::new();
+    let db = schema.connection();
+    seeds::countries::import(&db).unwrap();
+
+    let result = list(Some("south_america".into()), &db);
+    assert!(result.is_ok());
+    assert_eq!(result.unwrap().len(), 12);
+  }
+
+  #[test]
+  fn list_fails_when_continent_is_invalid() {
+    with_db(|db| {
+      assert_eq!(
+        list(Some("atlantis".into()), &db),
+        Err(ListError::InvalidParams(vec![ValidationError::ContinentIsInvalid])),
+      );
+    });
+  }
+}
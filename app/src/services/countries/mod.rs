@@ -0,0 +1,5 @@
+pub mod list;
+pub mod find;
+
+pub use list::{list, ListError, ValidationError as ListValidationError};
+pub use find::{find, FindError, ValidationError as FindValidationError};
@@ -1 +1,3 @@
+pub mod countries;
+pub mod organizations;
 pub mod teachers;
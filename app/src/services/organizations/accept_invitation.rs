@@ -0,0 +1,307 @@
+use chrono::Utc;
+use serde::{Serialize, Serializer};
+use db::prelude::*;
+use db::models::{Organization, OrganizationInvitation, Teacher};
+
+use crate::services::teachers::authenticate;
+use crate::{handle_authentication, handle_unexpected_err, handle_unit_of_work, make_serializable};
+
+#[derive(PartialEq, Debug)]
+pub enum ValidationError {
+  TokenIsBlank,
+  InvitationIsExpired,
+  InvitationIsAlreadyAccepted,
+  TeacherAlreadyBelongsToOrganization,
+}
+
+make_serializable!(ValidationError {
+  TokenIsBlank => ("token", "blank", "Invitation token can't be blank"),
+  InvitationIsExpired => ("token", "expired", "Invitation has expired"),
+  InvitationIsAlreadyAccepted =>
+    ("token", "already_accepted", "Invitation has already been accepted"),
+  TeacherAlreadyBelongsToOrganization =>
+    ("base", "already_in_organization", "You already belong to an organization"),
+});
+
+#[derive(PartialEq, Debug)]
+pub enum AcceptInvitationError {
+  InvalidParams(Vec<ValidationError>),
+  InvitationNotFound,
+  Unauthorized,
+  UnexpectedError,
+}
+
+struct AcceptInvitation<'a> {
+  token: String,
+  access_token: String,
+  db: &'a DbConnection,
+}
+
+impl<'a> AcceptInvitation<'a> {
+  fn new(token: String, access_token: String, db: &'a DbConnection) -> Self {
+    Self {
+      token,
+      access_token,
+      db,
+    }
+  }
+
+  fn validate_params(&self) -> Result<(), AcceptInvitationError> {
+    let mut errors = vec![];
+
+    if self.token.trim().is_empty() {
+      errors.push(ValidationError::TokenIsBlank);
+    }
+
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(AcceptInvitationError::InvalidParams(errors))
+    }
+  }
+
+  fn authenticate(&self) -> Result<Teacher, AcceptInvitationError> {
+    handle_authentication!(authenticate(&self.access_token, self.db), AcceptInvitationError)
+  }
+
+  fn get_invitation(&self, teacher: &Teacher) -> Result<OrganizationInvitation, AcceptInvitationError> {
+    match OrganizationInvitationsRepository::new(self.db).find_by_token(&self.token) {
+      // Invitations addressed to someone else are reported as missing,
+      // so that a leaked token doesn't reveal who was invited where
+      Ok(invitation) if invitation.email.eq_ignore_ascii_case(&teacher.email) => Ok(invitation),
+      Ok(_) | Err(DbError::RecordNotFound) => Err(AcceptInvitationError::InvitationNotFound),
+      Err(error) => handle_unexpected_err!(error, AcceptInvitationError::UnexpectedError),
+    }
+  }
+
+  fn validate_invitation(
+    &self,
+    invitation: &OrganizationInvitation,
+    teacher: &Teacher,
+  ) -> Result<(), AcceptInvitationError> {
+    let mut errors = vec![];
+
+    if invitation.accepted_at.is_some() {
+      errors.push(ValidationError::InvitationIsAlreadyAccepted);
+    } else if invitation.expires_at < Utc::now() {
+      errors.push(ValidationError::InvitationIsExpired);
+    }
+    if teacher.organization_id.is_some() {
+      errors.push(ValidationError::TeacherAlreadyBelongsToOrganization);
+    }
+
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(AcceptInvitationError::InvalidParams(errors))
+    }
+  }
+
+  fn get_organization(&self, invitation: &OrganizationInvitation) -> Result<Organization, AcceptInvitationError> {
+    match OrganizationsRepository::new(self.db).find_by_id(invitation.organization_id) {
+      Ok(organization) => Ok(organization),
+      Err(error) => handle_unexpected_err!(error, AcceptInvitationError::UnexpectedError),
+    }
+  }
+
+  fn accept_invitation(
+    &self,
+    invitation: &OrganizationInvitation,
+  ) -> Result<OrganizationInvitation, AcceptInvitationError> {
+    match OrganizationInvitationsRepository::new(self.db).accept(invitation) {
+      Ok(invitation) => Ok(invitation),
+      // Someone accepted the invitation after it was validated
+      Err(DbError::RecordNotFound) => Err(
+        AcceptInvitationError::InvalidParams(vec![ValidationError::InvitationIsAlreadyAccepted])
+      ),
+      Err(error) => handle_unexpected_err!(error, AcceptInvitationError::UnexpectedError),
+    }
+  }
+
+  fn join_organization(
+    &self,
+    invitation: &OrganizationInvitation,
+    teacher: &Teacher,
+    organization: &Organization,
+  ) -> Result<Teacher, AcceptInvitationError> {
+    let repository = TeachersRepository::new(self.db);
+
+    match repository.join_organization(teacher, organization, &invitation.role) {
+      Ok(teacher) => Ok(teacher),
+      Err(error) => handle_unexpected_err!(error, AcceptInvitationError::UnexpectedError),
+    }
+  }
+
+  fn call(self) -> Result<Teacher, AcceptInvitationError> {
+    self.validate_params()?;
+    let teacher = self.authenticate()?;
+    let invitation = self.get_invitation(&teacher)?;
+    self.validate_invitation(&invitation, &teacher)?;
+    let organization = self.get_organization(&invitation)?;
+    let invitation = self.accept_invitation(&invitation)?;
+    let teacher = self.join_organization(&invitation, &teacher, &organization)?;
+
+    Ok(teacher)
+  }
+}
+
+pub fn accept_invitation(
+  token: String,
+  access_token: String,
+  db: &DbConnection,
+) -> Result<Teacher, AcceptInvitationError> {
+  let unit_of_work = UnitOfWork::run(db, |unit_of_work| {
+    AcceptInvitation::new(token, access_token, unit_of_work.connection()).call()
+  });
+
+  handle_unit_of_work!(unit_of_work, AcceptInvitationError::UnexpectedError)
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Duration;
+  use db::models::Session;
+  use db::models::organization::MEMBER_ROLE;
+  use db::utils::test::{with_db, TestSchema};
+  use db::utils::test::factories::{unique_email, SessionFactory, TeacherFactory};
+  use super::*;
+
+  fn invite(db: &DbConnection, email: &str) -> OrganizationInvitation {
+    let organization = OrganizationsRepository::new(db).create("SP 1 Warszawa".into()).unwrap();
+
+    OrganizationInvitationsRepository::new(db)
+      .create(&organization, email.into(), MEMBER_ROLE.into())
+      .unwrap()
+  }
+
+  fn sign_in(db: &DbConnection, email: &str) -> Session {
+    let teacher = TeacherFactory::new(db).email(email).create();
+
+    SessionFactory::new(db, &teacher).create()
+  }
+
+  #[test]
+  fn accept_invitation_works() {
+    with_db(|db| {
+      let email = unique_email();
+      let invitation = invite(&db, &email);
+      let session = sign_in(&db, &email);
+
+      let result = accept_invitation(invitation.token.clone(), session.access_token, &db);
+      assert!(result.is_ok());
+      let teacher = result.unwrap();
+      assert_eq!(teacher.organization_id, Some(invitation.organization_id));
+      assert_eq!(teacher.organization_role, Some(MEMBER_ROLE.into()));
+      assert!(
+        OrganizationInvitationsRepository::new(&db)
+          .find_by_token(&invitation.token)
+          .unwrap()
+          .accepted_at
+          .is_some()
+      );
+    });
+  }
+
+  #[test]
+  fn accept_invitation_fails_when_token_is_blank() {
+    with_db(|db| {
+      let session = sign_in(&db, &unique_email());
+
+      assert_eq!(
+        accept_invitation("".into(), session.access_token, &db),
+        Err(AcceptInvitationError::InvalidParams(vec![ValidationError::TokenIsBlank])),
+      );
+    });
+  }
+
+  #[test]
+  fn accept_invitation_fails_when_access_token_is_invalid() {
+    with_db(|db| {
+      let invitation = invite(&db, &unique_email());
+
+      assert_eq!(
+        accept_invitation(invitation.token, "access_token".into(), &db),
+        Err(AcceptInvitationError::Unauthorized),
+      );
+    });
+  }
+
+  #[test]
+  fn accept_invitation_fails_when_invitation_doesnt_exist() {
+    with_db(|db| {
+      let session = sign_in(&db, &unique_email());
+
+      assert_eq!(
+        accept_invitation("token".into(), session.access_token, &db),
+        Err(AcceptInvitationError::InvitationNotFound),
+      );
+    });
+  }
+
+  #[test]
+  fn accept_invitation_fails_when_invitation_is_for_someone_else() {
+    with_db(|db| {
+      let invitation = invite(&db, &unique_email());
+      let session = sign_in(&db, &unique_email());
+
+      assert_eq!(
+        accept_invitation(invitation.token, session.access_token, &db),
+        Err(AcceptInvitationError::InvitationNotFound),
+      );
+    });
+  }
+
+  #[test]
+  fn accept_invitation_fails_when_invitation_is_expired() {
+    with_db(|db| {
+      let email = unique_email();
+      let mut invitation = invite(&db, &email);
+      let session = sign_in(&db, &email);
+      invitation.expires_at = Utc::now() - Duration::minutes(1);
+      OrganizationInvitationsRepository::new(&db).save(&invitation).unwrap();
+
+      assert_eq!(
+        accept_invitation(invitation.token, session.access_token, &db),
+        Err(AcceptInvitationError::InvalidParams(vec![ValidationError::InvitationIsExpired])),
+      );
+    });
+  }
+
+  #[test]
+  fn accept_invitation_fails_when_invitation_is_already_accepted() {
+    with_db(|db| {
+      let email = unique_email();
+      let invitation = invite(&db, &email);
+      let session = sign_in(&db, &email);
+      accept_invitation(invitation.token.clone(), session.access_token.clone(), &db).unwrap();
+
+      assert_eq!(
+        accept_invitation(invitation.token, session.access_token, &db),
+        Err(AcceptInvitationError::InvalidParams(vec![
+          ValidationError::InvitationIsAlreadyAccepted,
+          ValidationError::TeacherAlreadyBelongsToOrganization,
+        ])),
+      );
+    });
+  }
+
+  #[test]
+  fn accept_invitation_rolls_back_when_joining_organization_fails() {
+    let schema = TestSchema::new();
+    let db = schema.connection();
+    let email = unique_email();
+    let invitation = invite(&db, &email);
+    let session = sign_in(&db, &email);
+    // The invitation is saved first, the teacher is updated afterwards
+    schema.fail_writes_to("teachers");
+
+    assert_eq!(
+      accept_invitation(invitation.token.clone(), session.access_token, &db),
+      Err(AcceptInvitationError::UnexpectedError),
+    );
+    assert_eq!(
+      OrganizationInvitationsRepository::new(&db).find_by_token(&invitation.token).unwrap(),
+      invitation,
+    );
+  }
+}
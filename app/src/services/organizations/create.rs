@@ -0,0 +1,184 @@
+use serde::{Serialize, Serializer};
+use db::prelude::*;
+use db::models::{Organization, Teacher};
+use db::models::organization::ADMIN_ROLE;
+
+use crate::services::teachers::authenticate;
+use crate::{handle_authentication, handle_unexpected_err, handle_unit_of_work, make_serializable};
+
+#[derive(PartialEq, Debug)]
+pub enum ValidationError {
+  NameIsBlank,
+  TeacherAlreadyBelongsToOrganization,
+}
+
+make_serializable!(ValidationError {
+  NameIsBlank => ("name", "blank", "Name can't be blank"),
+  TeacherAlreadyBelongsToOrganization =>
+    ("base", "already_in_organization", "You already belong to an organization"),
+});
+
+#[derive(PartialEq, Debug)]
+pub enum CreateError {
+  InvalidParams(Vec<ValidationError>),
+  Unauthorized,
+  UnexpectedError,
+}
+
+struct Create<'a> {
+  name: String,
+  access_token: String,
+  db: &'a DbConnection,
+}
+
+impl<'a> Create<'a> {
+  fn new(name: String, access_token: String, db: &'a DbConnection) -> Self {
+    Self {
+      name,
+      access_token,
+      db,
+    }
+  }
+
+  fn validate_params(&self) -> Result<(), CreateError> {
+    let mut errors = vec![];
+
+    if self.name.trim().is_empty() {
+      errors.push(ValidationError::NameIsBlank);
+    }
+
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(CreateError::InvalidParams(errors))
+    }
+  }
+
+  fn authenticate(&self) -> Result<Teacher, CreateError> {
+    handle_authentication!(authenticate(&self.access_token, self.db), CreateError)
+  }
+
+  fn validate_teacher(&self, teacher: &Teacher) -> Result<(), CreateError> {
+    if teacher.organization_id.is_none() {
+      Ok(())
+    } else {
+      Err(
+        CreateError::InvalidParams(
+          vec![ValidationError::TeacherAlreadyBelongsToOrganization]
+        )
+      )
+    }
+  }
+
+  fn create_organization(&self, teacher: &Teacher) -> Result<Organization, CreateError> {
+    let organizations_repository = OrganizationsRepository::new(self.db);
+    let teachers_repository = TeachersRepository::new(self.db);
+
+    let organization = match organizations_repository.create(self.name.trim().to_string()) {
+      Ok(organization) => organization,
+      Err(error) => return handle_unexpected_err!(error, CreateError::UnexpectedError),
+    };
+
+    // The teacher who creates the organization becomes its first admin
+    match teachers_repository.join_organization(teacher, &organization, ADMIN_ROLE) {
+      Ok(_) => Ok(organization),
+      Err(error) => handle_unexpected_err!(error, CreateError::UnexpectedError),
+    }
+  }
+
+  fn call(self) -> Result<Organization, CreateError> {
+    self.validate_params()?;
+    let teacher = self.authenticate()?;
+    self.validate_teacher(&teacher)?;
+    let organization = self.create_organization(&teacher)?;
+
+    Ok(organization)
+  }
+}
+
+pub fn create(name: String, access_token: String, db: &DbConnection) -> Result<Organization, CreateError> {
+  let unit_of_work = UnitOfWork::run(db, |unit_of_work| {
+    Create::new(name, access_token, unit_of_work.connection()).call()
+  });
+
+  handle_unit_of_work!(unit_of_work, CreateError::UnexpectedError)
+}
+
+#[cfg(test)]
+mod tests {
+  use db::utils::test::{with_db, TestSchema};
+  use db::utils::test::factories::{SessionFactory, TeacherFactory};
+  use super::*;
+
+  #[test]
+  fn create_works() {
+    with_db(|db| {
+      let teachers_repository = TeachersRepository::new(&db);
+      let teacher = TeacherFactory::new(&db).create();
+      let session = SessionFactory::new(&db, &teacher).create();
+
+      let result = create("SP 1 Warszawa".into(), session.access_token, &db);
+      assert!(result.is_ok());
+      let organization = result.unwrap();
+      let teacher = teachers_repository.find_by_uuid(&teacher.uuid).unwrap();
+      assert_eq!(teacher.organization_id, Some(organization.id));
+      assert_eq!(teacher.organization_role, Some(ADMIN_ROLE.into()));
+    });
+  }
+
+  #[test]
+  fn create_fails_when_name_is_blank() {
+    with_db(|db| {
+      let teacher = TeacherFactory::new(&db).create();
+      let session = SessionFactory::new(&db, &teacher).create();
+
+      assert_eq!(
+        create(" ".into(), session.access_token, &db),
+        Err(CreateError::InvalidParams(vec![ValidationError::NameIsBlank])),
+      );
+      assert_eq!(OrganizationsRepository::new(&db).count().unwrap(), 0);
+    });
+  }
+
+  #[test]
+  fn create_fails_when_access_token_is_invalid() {
+    with_db(|db| {
+      assert_eq!(
+        create("SP 1 Warszawa".into(), "access_token".into(), &db),
+        Err(CreateError::Unauthorized),
+      );
+      assert_eq!(OrganizationsRepository::new(&db).count().unwrap(), 0);
+    });
+  }
+
+  #[test]
+  fn create_fails_when_teacher_already_belongs_to_organization() {
+    with_db(|db| {
+      let teacher = TeacherFactory::new(&db).create();
+      let session = SessionFactory::new(&db, &teacher).create();
+      create("SP 1 Warszawa".into(), session.access_token.clone(), &db).unwrap();
+
+      assert_eq!(
+        create("SP 2 Warszawa".into(), session.access_token, &db),
+        Err(CreateError::InvalidParams(vec![ValidationError::TeacherAlreadyBelongsToOrganization])),
+      );
+      assert_eq!(OrganizationsRepository::new(&db).count().unwrap(), 1);
+    });
+  }
+
+  #[test]
+  fn create_rolls_back_when_joining_organization_fails() {
+    let schema = TestSchema::new();
+    let db = schema.connection();
+    let teacher = TeacherFactory::new(&db).create();
+    let session = SessionFactory::new(&db, &teacher).create();
+    // The organization is created first, the teacher is updated afterwards
+    schema.fail_writes_to("teachers");
+
+    assert_eq!(
+      create("SP 1 Warszawa".into(), session.access_token, &db),
+      Err(CreateError::UnexpectedError),
+    );
+    assert_eq!(OrganizationsRepository::new(&db).count().unwrap(), 0);
+  }
+}
@@ -0,0 +1,266 @@
+use serde::{Serialize, Serializer};
+use db::prelude::*;
+use db::models::{Organization, Teacher};
+
+use crate::services::teachers::{authenticate, authorize_admin, AuthorizationError};
+use crate::{handle_authentication, handle_unexpected_err, handle_unit_of_work, make_serializable};
+
+#[derive(PartialEq, Debug)]
+pub enum ValidationError {
+  OrganizationUuidIsBlank,
+  TeacherUuidIsBlank,
+  CannotDeactivateYourself,
+}
+
+make_serializable!(ValidationError {
+  OrganizationUuidIsBlank => ("organization_uuid", "blank", "Organization UUID can't be blank"),
+  TeacherUuidIsBlank => ("teacher_uuid", "blank", "Teacher UUID can't be blank"),
+  CannotDeactivateYourself =>
+    ("teacher_uuid", "self_deactivation", "You can't deactivate your own account"),
+});
+
+#[derive(PartialEq, Debug)]
+pub enum DeactivateMemberError {
+  InvalidParams(Vec<ValidationError>),
+  OrganizationNotFound,
+  TeacherNotFound,
+  Unauthorized,
+  Forbidden,
+  UnexpectedError,
+}
+
+struct DeactivateMember<'a> {
+  organization_uuid: String,
+  teacher_uuid: String,
+  access_token: String,
+  db: &'a DbConnection,
+}
+
+impl<'a> DeactivateMember<'a> {
+  fn new(
+    organization_uuid: String,
+    teacher_uuid: String,
+    access_token: String,
+    db: &'a DbConnection,
+  ) -> Self {
+    Self {
+      organization_uuid,
+      teacher_uuid,
+      access_token,
+      db,
+    }
+  }
+
+  fn validate_params(&self) -> Result<(), DeactivateMemberError> {
+    let mut errors = vec![];
+
+    if self.organization_uuid.trim().is_empty() {
+      errors.push(ValidationError::OrganizationUuidIsBlank);
+    }
+    if self.teacher_uuid.trim().is_empty() {
+      errors.push(ValidationError::TeacherUuidIsBlank);
+    }
+
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(DeactivateMemberError::InvalidParams(errors))
+    }
+  }
+
+  fn authenticate(&self) -> Result<Teacher, DeactivateMemberError> {
+    handle_authentication!(authenticate(&self.access_token, self.db), DeactivateMemberError)
+  }
+
+  fn get_organization(&self) -> Result<Organization, DeactivateMemberError> {
+    match OrganizationsRepository::new(self.db).find_by_uuid(&self.organization_uuid) {
+      Ok(organization) => Ok(organization),
+      Err(DbError::RecordNotFound) => Err(DeactivateMemberError::OrganizationNotFound),
+      Err(error) => handle_unexpected_err!(error, DeactivateMemberError::UnexpectedError),
+    }
+  }
+
+  fn authorize(&self, teacher: &Teacher, organization: &Organization) -> Result<(), DeactivateMemberError> {
+    authorize_admin(teacher, organization)
+      .map_err(|AuthorizationError::Forbidden| DeactivateMemberError::Forbidden)
+  }
+
+  fn get_member(&self, admin: &Teacher, organization: &Organization) -> Result<Teacher, DeactivateMemberError> {
+    if admin.uuid == self.teacher_uuid {
+      return Err(
+        DeactivateMemberError::InvalidParams(
+          vec![ValidationError::CannotDeactivateYourself]
+        )
+      );
+    }
+
+    match TeachersRepository::new(self.db).find_by_uuid(&self.teacher_uuid) {
+      Ok(teacher) if teacher.organization_id == Some(organization.id) => Ok(teacher),
+      Ok(_) | Err(DbError::RecordNotFound) => Err(DeactivateMemberError::TeacherNotFound),
+      Err(error) => handle_unexpected_err!(error, DeactivateMemberError::UnexpectedError),
+    }
+  }
+
+  fn deactivate_member(&self, teacher: &Teacher) -> Result<(), DeactivateMemberError> {
+    let teachers_repository = TeachersRepository::new(self.db);
+    let sessions_repository = SessionsRepository::new(self.db);
+
+    if let Err(error) = teachers_repository.deactivate(teacher) {
+      return handle_unexpected_err!(error, DeactivateMemberError::UnexpectedError);
+    }
+
+    // Sign the teacher out everywhere, so that already issued tokens stop working
+    match sessions_repository.destroy_all_by_owner(teacher) {
+      Ok(_) => Ok(()),
+      Err(error) => handle_unexpected_err!(error, DeactivateMemberError::UnexpectedError),
+    }
+  }
+
+  fn call(self) -> Result<(), DeactivateMemberError> {
+    self.validate_params()?;
+    let admin = self.authenticate()?;
+    let organization = self.get_organization()?;
+    self.authorize(&admin, &organization)?;
+    let member = self.get_member(&admin, &organization)?;
+    self.deactivate_member(&member)?;
+
+    Ok(())
+  }
+}
+
+pub fn deactivate_member(
+  organization_uuid: String,
+  teacher_uuid: String,
+  access_token: String,
+  db: &DbConnection,
+) -> Result<(), DeactivateMemberError> {
+  let unit_of_work = UnitOfWork::run(db, |unit_of_work| {
+    let connection = unit_of_work.connection();
+    DeactivateMember::new(organization_uuid, teacher_uuid, access_token, connection).call()
+  });
+
+  handle_unit_of_work!(unit_of_work, DeactivateMemberError::UnexpectedError)
+}
+
+#[cfg(test)]
+mod tests {
+  use db::models::organization::{ADMIN_ROLE, MEMBER_ROLE};
+  use db::utils::test::{with_db, TestSchema};
+  use db::utils::test::factories::{sign_in_member, TeacherFactory};
+  use super::*;
+
+  #[test]
+  fn deactivate_member_works() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+      let (_, session) = sign_in_member(&db, &organization, ADMIN_ROLE);
+      let (member, _) = sign_in_member(&db, &organization, MEMBER_ROLE);
+
+      let result = deactivate_member(organization.uuid, member.uuid.clone(), session.access_token, &db);
+      assert!(result.is_ok());
+      assert!(TeachersRepository::new(&db).find_by_uuid(&member.uuid).unwrap().deactivated_at.is_some());
+      assert_eq!(SessionsRepository::new(&db).count().unwrap(), 1);
+    });
+  }
+
+  #[test]
+  fn deactivate_member_fails_when_params_are_blank() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+      let (_, session) = sign_in_member(&db, &organization, ADMIN_ROLE);
+
+      assert_eq!(
+        deactivate_member("".into(), "".into(), session.access_token, &db),
+        Err(DeactivateMemberError::InvalidParams(vec![
+          ValidationError::OrganizationUuidIsBlank,
+          ValidationError::TeacherUuidIsBlank,
+        ])),
+      );
+    });
+  }
+
+  #[test]
+  fn deactivate_member_fails_when_access_token_is_invalid() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+      let (member, _) = sign_in_member(&db, &organization, MEMBER_ROLE);
+
+      assert_eq!(
+        deactivate_member(organization.uuid, member.uuid, "access_token".into(), &db),
+        Err(DeactivateMemberError::Unauthorized),
+      );
+    });
+  }
+
+  #[test]
+  fn deactivate_member_fails_when_organization_doesnt_exist() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+      let (_, session) = sign_in_member(&db, &organization, ADMIN_ROLE);
+      let (member, _) = sign_in_member(&db, &organization, MEMBER_ROLE);
+
+      assert_eq!(
+        deactivate_member("uuid".into(), member.uuid, session.access_token, &db),
+        Err(DeactivateMemberError::OrganizationNotFound),
+      );
+    });
+  }
+
+  #[test]
+  fn deactivate_member_fails_when_teacher_is_not_an_admin() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+      let (_, session) = sign_in_member(&db, &organization, MEMBER_ROLE);
+      let (other_member, _) = sign_in_member(&db, &organization, MEMBER_ROLE);
+
+      assert_eq!(
+        deactivate_member(organization.uuid, other_member.uuid, session.access_token, &db),
+        Err(DeactivateMemberError::Forbidden),
+      );
+    });
+  }
+
+  #[test]
+  fn deactivate_member_fails_when_teacher_is_not_a_member() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+      let (_, session) = sign_in_member(&db, &organization, ADMIN_ROLE);
+      let teacher = TeacherFactory::new(&db).create();
+
+      assert_eq!(
+        deactivate_member(organization.uuid, teacher.uuid, session.access_token, &db),
+        Err(DeactivateMemberError::TeacherNotFound),
+      );
+    });
+  }
+
+  #[test]
+  fn deactivate_member_fails_when_deactivating_yourself() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+      let (admin, session) = sign_in_member(&db, &organization, ADMIN_ROLE);
+
+      assert_eq!(
+        deactivate_member(organization.uuid, admin.uuid, session.access_token, &db),
+        Err(DeactivateMemberError::InvalidParams(vec![ValidationError::CannotDeactivateYourself])),
+      );
+    });
+  }
+
+  #[test]
+  fn deactivate_member_rolls_back_when_signing_out_fails() {
+    let schema = TestSchema::new();
+    let db = schema.connection();
+    let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+    let (_, session) = sign_in_member(&db, &organization, ADMIN_ROLE);
+    let (member, _) = sign_in_member(&db, &organization, MEMBER_ROLE);
+    // The member is deactivated first, their sessions are destroyed afterwards
+    schema.fail_writes_to("sessions");
+
+    assert_eq!(
+      deactivate_member(organization.uuid, member.uuid.clone(), session.access_token, &db),
+      Err(DeactivateMemberError::UnexpectedError),
+    );
+    assert_eq!(TeachersRepository::new(&db).find_by_uuid(&member.uuid).unwrap(), member);
+  }
+}
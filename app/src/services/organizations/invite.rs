@@ -0,0 +1,226 @@
+use serde::{Serialize, Serializer};
+use db::prelude::*;
+use db::models::{Organization, OrganizationInvitation, Teacher};
+use db::models::organization::{MEMBER_ROLE, ROLES};
+
+use crate::services::teachers::{authenticate, authorize_admin, AuthorizationError};
+use crate::utils::constants::EMAIL_REGEX;
+use crate::{handle_authentication, handle_unexpected_err, make_serializable};
+
+#[derive(PartialEq, Debug)]
+pub enum ValidationError {
+  OrganizationUuidIsBlank,
+  EmailIsBlank,
+  EmailIsInvalid,
+  RoleIsInvalid,
+}
+
+make_serializable!(ValidationError {
+  OrganizationUuidIsBlank => ("organization_uuid", "blank", "Organization UUID can't be blank"),
+  EmailIsBlank => ("email", "blank", "Email can't be blank"),
+  EmailIsInvalid => ("email", "invalid", "Email is invalid"),
+  RoleIsInvalid => ("role", "invalid", "Role is invalid"),
+});
+
+#[derive(PartialEq, Debug)]
+pub enum InviteError {
+  InvalidParams(Vec<ValidationError>),
+  OrganizationNotFound,
+  Unauthorized,
+  Forbidden,
+  UnexpectedError,
+}
+
+struct Invite<'a> {
+  organization_uuid: String,
+  email: String,
+  role: String,
+  access_token: String,
+  db: &'a DbConnection,
+}
+
+impl<'a> Invite<'a> {
+  fn new(
+    organization_uuid: String,
+    email: String,
+    role: Option<String>,
+    access_token: String,
+    db: &'a DbConnection,
+  ) -> Self {
+    Self {
+      organization_uuid,
+      email,
+      role: role.unwrap_or_else(|| String::from(MEMBER_ROLE)),
+      access_token,
+      db,
+    }
+  }
+
+  fn validate_params(&self) -> Result<(), InviteError> {
+    let mut errors = vec![];
+
+    if self.organization_uuid.trim().is_empty() {
+      errors.push(ValidationError::OrganizationUuidIsBlank);
+    }
+    if self.email.trim().is_empty() {
+      errors.push(ValidationError::EmailIsBlank);
+    } else if !EMAIL_REGEX.is_match(&self.email) {
+      errors.push(ValidationError::EmailIsInvalid);
+    }
+    if !ROLES.contains(&self.role.as_str()) {
+      errors.push(ValidationError::RoleIsInvalid);
+    }
+
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(InviteError::InvalidParams(errors))
+    }
+  }
+
+  fn authenticate(&self) -> Result<Teacher, InviteError> {
+    handle_authentication!(authenticate(&self.access_token, self.db), InviteError)
+  }
+
+  fn get_organization(&self) -> Result<Organization, InviteError> {
+    match OrganizationsRepository::new(self.db).find_by_uuid(&self.organization_uuid) {
+      Ok(organization) => Ok(organization),
+      Err(DbError::RecordNotFound) => Err(InviteError::OrganizationNotFound),
+      Err(error) => handle_unexpected_err!(error, InviteError::UnexpectedError),
+    }
+  }
+
+  fn authorize(&self, teacher: &Teacher, organization: &Organization) -> Result<(), InviteError> {
+    authorize_admin(teacher, organization)
+      .map_err(|AuthorizationError::Forbidden| InviteError::Forbidden)
+  }
+
+  fn create_invitation(&self, organization: &Organization) -> Result<OrganizationInvitation, InviteError> {
+    let repository = OrganizationInvitationsRepository::new(self.db);
+
+    match repository.create(organization, self.email.clone(), self.role.clone()) {
+      Ok(invitation) => Ok(invitation),
+      Err(error) => handle_unexpected_err!(error, InviteError::UnexpectedError),
+    }
+  }
+
+  fn call(self) -> Result<OrganizationInvitation, InviteError> {
+    self.validate_params()?;
+    let teacher = self.authenticate()?;
+    let organization = self.get_organization()?;
+    self.authorize(&teacher, &organization)?;
+    let invitation = self.create_invitation(&organization)?;
+
+    Ok(invitation)
+  }
+}
+
+pub fn invite(
+  organization_uuid: String,
+  email: String,
+  role: Option<String>,
+  access_token: String,
+  db: &DbConnection,
+) -> Result<OrganizationInvitation, InviteError> {
+  Invite::new(organization_uuid, email, role, access_token, db).call()
+}
+
+#[cfg(test)]
+mod tests {
+  use db::models::Session;
+  use db::models::organization::ADMIN_ROLE;
+  use db::utils::test::with_db;
+  use db::utils::test::factories::{sign_in_member, unique_email};
+  use super::*;
+
+  fn sign_in_to_organization(db: &DbConnection, role: &str) -> (Organization, Session) {
+    let organization = OrganizationsRepository::new(db).create("SP 1 Warszawa".into()).unwrap();
+    let (_, session) = sign_in_member(db, &organization, role);
+
+    (organization, session)
+  }
+
+  #[test]
+  fn invite_works() {
+    with_db(|db| {
+      let (organization, session) = sign_in_to_organization(&db, ADMIN_ROLE);
+
+      let result = invite(
+        organization.uuid,
+        unique_email(),
+        None,
+        session.access_token,
+        &db,
+      );
+      assert!(result.is_ok());
+      let invitation = result.unwrap();
+      assert_eq!(invitation.organization_id, organization.id);
+      assert_eq!(invitation.role, MEMBER_ROLE);
+    });
+  }
+
+  #[test]
+  fn invite_fails_when_params_are_invalid() {
+    with_db(|db| {
+      let (_, session) = sign_in_to_organization(&db, ADMIN_ROLE);
+
+      assert_eq!(
+        invite("".into(), "john.doe".into(), Some("owner".into()), session.access_token, &db),
+        Err(InviteError::InvalidParams(vec![
+          ValidationError::OrganizationUuidIsBlank,
+          ValidationError::EmailIsInvalid,
+          ValidationError::RoleIsInvalid,
+        ])),
+      );
+    });
+  }
+
+  #[test]
+  fn invite_fails_when_email_is_blank() {
+    with_db(|db| {
+      let (organization, session) = sign_in_to_organization(&db, ADMIN_ROLE);
+
+      assert_eq!(
+        invite(organization.uuid, "".into(), None, session.access_token, &db),
+        Err(InviteError::InvalidParams(vec![ValidationError::EmailIsBlank])),
+      );
+    });
+  }
+
+  #[test]
+  fn invite_fails_when_access_token_is_invalid() {
+    with_db(|db| {
+      let (organization, _) = sign_in_to_organization(&db, ADMIN_ROLE);
+
+      assert_eq!(
+        invite(organization.uuid, unique_email(), None, "access_token".into(), &db),
+        Err(InviteError::Unauthorized),
+      );
+    });
+  }
+
+  #[test]
+  fn invite_fails_when_organization_doesnt_exist() {
+    with_db(|db| {
+      let (_, session) = sign_in_to_organization(&db, ADMIN_ROLE);
+
+      assert_eq!(
+        invite("uuid".into(), unique_email(), None, session.access_token, &db),
+        Err(InviteError::OrganizationNotFound),
+      );
+    });
+  }
+
+  #[test]
+  fn invite_fails_when_teacher_is_not_an_admin() {
+    with_db(|db| {
+      let (organization, session) = sign_in_to_organization(&db, MEMBER_ROLE);
+
+      assert_eq!(
+        invite(organization.uuid, unique_email(), None, session.access_token, &db),
+        Err(InviteError::Forbidden),
+      );
+      assert_eq!(OrganizationInvitationsRepository::new(&db).count().unwrap(), 0);
+    });
+  }
+}
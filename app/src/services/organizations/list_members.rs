@@ -0,0 +1,190 @@
+use serde::{Serialize, Serializer};
+use db::prelude::*;
+use db::models::{Organization, Teacher};
+
+use crate::services::teachers::{authenticate, authorize_admin, AuthorizationError};
+use crate::{handle_authentication, handle_unexpected_err, make_serializable};
+
+#[derive(PartialEq, Debug)]
+pub enum ValidationError {
+  OrganizationUuidIsBlank,
+}
+
+make_serializable!(ValidationError {
+  OrganizationUuidIsBlank => ("organization_uuid", "blank", "Organization UUID can't be blank"),
+});
+
+#[derive(PartialEq, Debug)]
+pub enum ListMembersError {
+  InvalidParams(Vec<ValidationError>),
+  OrganizationNotFound,
+  Unauthorized,
+  Forbidden,
+  UnexpectedError,
+}
+
+struct ListMembers<'a> {
+  organization_uuid: String,
+  access_token: String,
+  db: &'a DbConnection,
+}
+
+impl<'a> ListMembers<'a> {
+  fn new(organization_uuid: String, access_token: String, db: &'a DbConnection) -> Self {
+    Self {
+      organization_uuid,
+      access_token,
+      db,
+    }
+  }
+
+  fn validate_params(&self) -> Result<(), ListMembersError> {
+    let mut errors = vec![];
+
+    if self.organization_uuid.trim().is_empty() {
+      errors.push(ValidationError::OrganizationUuidIsBlank);
+    }
+
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(ListMembersError::InvalidParams(errors))
+    }
+  }
+
+  fn authenticate(&self) -> Result<Teacher, ListMembersError> {
+    handle_authentication!(authenticate(&self.access_token, self.db), ListMembersError)
+  }
+
+  fn get_organization(&self) -> Result<Organization, ListMembersError> {
+    match OrganizationsRepository::new(self.db).find_by_uuid(&self.organization_uuid) {
+      Ok(organization) => Ok(organization),
+      Err(DbError::RecordNotFound) => Err(ListMembersError::OrganizationNotFound),
+      Err(error) => handle_unexpected_err!(error, ListMembersError::UnexpectedError),
+    }
+  }
+
+  fn authorize(&self, teacher: &Teacher, organization: &Organization) -> Result<(), ListMembersError> {
+    authorize_admin(teacher, organization)
+      .map_err(|AuthorizationError::Forbidden| ListMembersError::Forbidden)
+  }
+
+  fn get_members(&self, organization: &Organization) -> Result<Vec<Teacher>, ListMembersError> {
+    match TeachersRepository::new(self.db).by_organization(organization) {
+      Ok(members) => Ok(members),
+      Err(error) => handle_unexpected_err!(error, ListMembersError::UnexpectedError),
+    }
+  }
+
+  fn call(self) -> Result<Vec<Teacher>, ListMembersError> {
+    self.validate_params()?;
+    let teacher = self.authenticate()?;
+    let organization = self.get_organization()?;
+    self.authorize(&teacher, &organization)?;
+    let members = self.get_members(&organization)?;
+
+    Ok(members)
+  }
+}
+
+pub fn list_members(
+  organization_uuid: String,
+  access_token: String,
+  db: &DbConnection,
+) -> Result<Vec<Teacher>, ListMembersError> {
+  ListMembers::new(organization_uuid, access_token, db).call()
+}
+
+#[cfg(test)]
+mod tests {
+  use db::models::organization::{ADMIN_ROLE, MEMBER_ROLE};
+  use db::utils::test::with_db;
+  use db::utils::test::factories::{sign_in_member, TeacherFactory};
+  use super::*;
+
+  #[test]
+  fn list_members_works() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+      let (admin, session) = sign_in_member(&db, &organization, ADMIN_ROLE);
+      let (member, _) = sign_in_member(&db, &organization, MEMBER_ROLE);
+      TeacherFactory::new(&db).create();
+      // The members are ordered by their emails
+      let mut emails = vec![admin.email, member.email];
+      emails.sort();
+
+      let result = list_members(organization.uuid, session.access_token, &db);
+      assert!(result.is_ok());
+      assert_eq!(
+        result.unwrap().into_iter().map(|teacher| teacher.email).collect::<Vec<String>>(),
+        emails,
+      );
+    });
+  }
+
+  #[test]
+  fn list_members_fails_when_organization_uuid_is_blank() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+      let (_, session) = sign_in_member(&db, &organization, ADMIN_ROLE);
+
+      assert_eq!(
+        list_members("".into(), session.access_token, &db),
+        Err(ListMembersError::InvalidParams(vec![ValidationError::OrganizationUuidIsBlank])),
+      );
+    });
+  }
+
+  #[test]
+  fn list_members_fails_when_access_token_is_invalid() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+
+      assert_eq!(
+        list_members(organization.uuid, "access_token".into(), &db),
+        Err(ListMembersError::Unauthorized),
+      );
+    });
+  }
+
+  #[test]
+  fn list_members_fails_when_organization_doesnt_exist() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+      let (_, session) = sign_in_member(&db, &organization, ADMIN_ROLE);
+
+      assert_eq!(
+        list_members("uuid".into(), session.access_token, &db),
+        Err(ListMembersError::OrganizationNotFound),
+      );
+    });
+  }
+
+  #[test]
+  fn list_members_fails_when_teacher_is_not_an_admin() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+      let (_, session) = sign_in_member(&db, &organization, MEMBER_ROLE);
+
+      assert_eq!(
+        list_members(organization.uuid, session.access_token, &db),
+        Err(ListMembersError::Forbidden),
+      );
+    });
+  }
+
+  #[test]
+  fn list_members_fails_when_teacher_is_an_admin_elsewhere() {
+    with_db(|db| {
+      let organizations_repository = OrganizationsRepository::new(&db);
+      let organization = organizations_repository.create("SP 1 Warszawa".into()).unwrap();
+      let other_organization = organizations_repository.create("SP 2 Warszawa".into()).unwrap();
+      let (_, session) = sign_in_member(&db, &other_organization, ADMIN_ROLE);
+
+      assert_eq!(
+        list_members(organization.uuid, session.access_token, &db),
+        Err(ListMembersError::Forbidden),
+      );
+    });
+  }
+}
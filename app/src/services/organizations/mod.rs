@@ -0,0 +1,19 @@
+pub mod create;
+pub mod invite;
+pub mod accept_invitation;
+pub mod list_members;
+pub mod deactivate_member;
+
+pub use create::{create, CreateError, ValidationError as CreateValidationError};
+pub use invite::{invite, InviteError, ValidationError as InviteValidationError};
+pub use accept_invitation::{
+  accept_invitation,
+  AcceptInvitationError,
+  ValidationError as AcceptInvitationValidationError,
+};
+pub use list_members::{list_members, ListMembersError, ValidationError as ListMembersValidationError};
+pub use deactivate_member::{
+  deactivate_member,
+  DeactivateMemberError,
+  ValidationError as DeactivateMemberValidationError,
+};
@@ -0,0 +1,182 @@
+use chrono::Utc;
+use db::prelude::*;
+use db::models::{Organization, Session, Teacher};
+use db::models::organization::ADMIN_ROLE;
+
+use crate::handle_unexpected_err;
+
+#[derive(PartialEq, Debug)]
+pub enum AuthenticationError {
+  Unauthorized,
+  UnexpectedError,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum AuthorizationError {
+  Forbidden,
+}
+
+struct Authenticate<'a> {
+  access_token: &'a str,
+  sessions_repository: SessionsRepository<'a>,
+  teachers_repository: TeachersRepository<'a>,
+}
+
+impl<'a> Authenticate<'a> {
+  fn new(access_token: &'a str, db: &'a DbConnection) -> Self {
+    Self {
+      sessions_repository: SessionsRepository::new(db),
+      teachers_repository: TeachersRepository::new(db),
+      access_token,
+    }
+  }
+
+  fn get_session(&self) -> Result<Session, AuthenticationError> {
+    if self.access_token.trim().is_empty() {
+      return Err(AuthenticationError::Unauthorized);
+    }
+
+    match self.sessions_repository.find_by_access_token(self.access_token) {
+      Ok(session) => Ok(session),
+      Err(DbError::RecordNotFound) => Err(AuthenticationError::Unauthorized),
+      Err(error) => handle_unexpected_err!(error, AuthenticationError::UnexpectedError),
+    }
+  }
+
+  fn validate_session(&self, session: &Session) -> Result<(), AuthenticationError> {
+    if session.owner_type == "teacher" && session.access_token_expires_at > Utc::now() {
+      Ok(())
+    } else {
+      Err(AuthenticationError::Unauthorized)
+    }
+  }
+
+  fn get_teacher(&self, session: &Session) -> Result<Teacher, AuthenticationError> {
+    match self.teachers_repository.find_by_uuid(&session.owner_uuid) {
+      Ok(teacher) => Ok(teacher),
+      Err(DbError::RecordNotFound) => Err(AuthenticationError::Unauthorized),
+      Err(error) => handle_unexpected_err!(error, AuthenticationError::UnexpectedError),
+    }
+  }
+
+  fn validate_teacher(&self, teacher: &Teacher) -> Result<(), AuthenticationError> {
+    if teacher.deactivated_at.is_none() {
+      Ok(())
+    } else {
+      Err(AuthenticationError::Unauthorized)
+    }
+  }
+
+  fn call(self) -> Result<Teacher, AuthenticationError> {
+    let session = self.get_session()?;
+    self.validate_session(&session)?;
+    let teacher = self.get_teacher(&session)?;
+    self.validate_teacher(&teacher)?;
+
+    Ok(teacher)
+  }
+}
+
+// Resolves the teacher behind an access token, for services that require a signed in teacher
+pub fn authenticate(access_token: &str, db: &DbConnection) -> Result<Teacher, AuthenticationError> {
+  Authenticate::new(access_token, db).call()
+}
+
+// Checks that an authenticated teacher administers the organization, for services managing it
+pub fn authorize_admin(
+  teacher: &Teacher,
+  organization: &Organization,
+) -> Result<(), AuthorizationError> {
+  if teacher.organization_id == Some(organization.id)
+    && teacher.organization_role.as_deref() == Some(ADMIN_ROLE) {
+    Ok(())
+  } else {
+    Err(AuthorizationError::Forbidden)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use db::models::organization::MEMBER_ROLE;
+  use db::utils::test::with_db;
+  use db::utils::test::factories::{SessionFactory, TeacherFactory};
+  use super::*;
+
+  #[test]
+  fn authenticate_works() {
+    with_db(|db| {
+      let teacher = TeacherFactory::new(&db).create();
+      let session = SessionFactory::new(&db, &teacher).create();
+
+      let result = authenticate(&session.access_token, &db);
+      assert!(result.is_ok());
+      assert_eq!(result.unwrap().id, teacher.id);
+    });
+  }
+
+  #[test]
+  fn authenticate_fails_when_access_token_is_blank() {
+    with_db(|db| {
+      assert_eq!(authenticate("", &db), Err(AuthenticationError::Unauthorized));
+    });
+  }
+
+  #[test]
+  fn authenticate_fails_when_session_doesnt_exist() {
+    with_db(|db| {
+      assert_eq!(authenticate("access_token", &db), Err(AuthenticationError::Unauthorized));
+    });
+  }
+
+  #[test]
+  fn authenticate_fails_when_access_token_is_expired() {
+    with_db(|db| {
+      let teacher = TeacherFactory::new(&db).create();
+      let session = SessionFactory::new(&db, &teacher).access_token_expired().create();
+
+      assert_eq!(authenticate(&session.access_token, &db), Err(AuthenticationError::Unauthorized));
+    });
+  }
+
+  #[test]
+  fn authenticate_fails_when_teacher_is_deactivated() {
+    with_db(|db| {
+      let teacher = TeacherFactory::new(&db).deactivated().create();
+      let session = SessionFactory::new(&db, &teacher).create();
+
+      assert_eq!(authenticate(&session.access_token, &db), Err(AuthenticationError::Unauthorized));
+    });
+  }
+
+  #[test]
+  fn authorize_admin_works() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+      let teacher = TeacherFactory::new(&db).organization(&organization, ADMIN_ROLE).create();
+
+      assert_eq!(authorize_admin(&teacher, &organization), Ok(()));
+    });
+  }
+
+  #[test]
+  fn authorize_admin_fails_when_teacher_is_a_member() {
+    with_db(|db| {
+      let organization = OrganizationsRepository::new(&db).create("SP 1 Warszawa".into()).unwrap();
+      let teacher = TeacherFactory::new(&db).organization(&organization, MEMBER_ROLE).create();
+
+      assert_eq!(authorize_admin(&teacher, &organization), Err(AuthorizationError::Forbidden));
+    });
+  }
+
+  #[test]
+  fn authorize_admin_fails_when_teacher_administers_another_organization() {
+    with_db(|db| {
+      let organizations_repository = OrganizationsRepository::new(&db);
+      let organization = organizations_repository.create("SP 1 Warszawa".into()).unwrap();
+      let other_organization = organizations_repository.create("SP 2 Warszawa".into()).unwrap();
+      let teacher = TeacherFactory::new(&db).organization(&other_organization, ADMIN_ROLE).create();
+
+      assert_eq!(authorize_admin(&teacher, &organization), Err(AuthorizationError::Forbidden));
+    });
+  }
+}
@@ -1,4 +1,6 @@
+mod authenticate;
 mod sign_up;
 pub mod sessions;
 
+pub use authenticate::{authenticate, authorize_admin, AuthenticationError, AuthorizationError};
 pub use sign_up::{sign_up, SignUpError, ValidationError as SignUpValidationError};
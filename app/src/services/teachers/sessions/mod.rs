@@ -2,6 +2,6 @@ pub mod sign_in;
 pub mod refresh;
 pub mod sign_out;
 
-pub use sign_in::{sign_in, SignInError, ValidationError as SignInValidationError};
+pub use sign_in::{sign_in, sign_in_async, SignInError, ValidationError as SignInValidationError};
 pub use refresh::{refresh, RefreshError, ValidationError as RefreshValidationError};
 pub use sign_out::{sign_out, SignOutError, ValidationError as SignOutValidationError};
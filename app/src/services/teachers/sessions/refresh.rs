@@ -1,7 +1,7 @@
-use chrono::{Duration, Utc};
+use chrono::Utc;
 use serde::{Serialize, Serializer};
 use db::prelude::*;
-use db::models::Session;
+use db::models::{Session, SessionLifetimes};
 use db::utils::token;
 
 use crate::{report_unexpected_err, handle_unexpected_err, make_serializable};
@@ -13,8 +13,8 @@ pub enum ValidationError {
 }
 
 make_serializable!(ValidationError {
-  SessionUuidIsBlank => "Session UUID can't be blank",
-  RefreshTokenIsBlank => "Refresh token can't be blank"
+  SessionUuidIsBlank => ("session_uuid", "blank", "Session UUID can't be blank"),
+  RefreshTokenIsBlank => ("refresh_token", "blank", "Refresh token can't be blank"),
 });
 
 #[derive(PartialEq, Debug)]
@@ -28,15 +28,22 @@ pub enum RefreshError {
 struct Refresh<'a> {
   session_uuid: String,
   refresh_token: String,
+  lifetimes: &'a SessionLifetimes,
   sessions_repository: SessionsRepository<'a>,
 }
 
 impl<'a> Refresh<'a> {
-  fn new(session_uuid: String, refresh_token: String, db: &'a DbConnection) -> Self {
+  fn new(
+    session_uuid: String,
+    refresh_token: String,
+    lifetimes: &'a SessionLifetimes,
+    db: &'a DbConnection,
+  ) -> Self {
     Self {
       sessions_repository: SessionsRepository::new(db),
       session_uuid,
       refresh_token,
+      lifetimes,
     }
   }
 
@@ -75,7 +82,7 @@ impl<'a> Refresh<'a> {
 
   fn update_session(&self, session: &mut Session) -> Result<(), RefreshError> {
     session.access_token = token::generate();
-    session.access_token_expires_at = Utc::now() + Duration::days(1);
+    session.access_token_expires_at = Utc::now() + self.lifetimes.access_token;
 
     match self.sessions_repository.save(session) {
       Ok(_) => Ok(()),
@@ -93,85 +100,77 @@ impl<'a> Refresh<'a> {
   }
 }
 
-pub fn refresh(session_uuid: String, refresh_token: String, db: &DbConnection) -> Result<Session, RefreshError> {
-  Refresh::new(session_uuid, refresh_token, db).call()
+pub fn refresh(
+  session_uuid: String,
+  refresh_token: String,
+  lifetimes: &SessionLifetimes,
+  db: &DbConnection,
+) -> Result<Session, RefreshError> {
+  Refresh::new(session_uuid, refresh_token, lifetimes, db).call()
 }
 
 #[cfg(test)]
 mod tests {
-  use serial_test::serial;
   use db::utils::test::with_db;
+  use db::utils::test::factories::{SessionFactory, TeacherFactory};
   use super::*;
 
   #[test]
-  #[serial]
   fn refresh_works() {
     with_db(|db| {
-      let teachers_repository = TeachersRepository::new(&db);
-      let sessions_repository = SessionsRepository::new(&db);
-      let teacher = teachers_repository.create("john.doe@example.com".into(), "test".into()).unwrap();
-      let session = sessions_repository.create(&teacher).unwrap();
+      let teacher = TeacherFactory::new(&db).create();
+      let session = SessionFactory::new(&db, &teacher).create();
 
-      let result = refresh(session.uuid, session.refresh_token, &db);
+      let result = refresh(session.uuid, session.refresh_token, &SessionLifetimes::default(), &db);
       assert!(result.is_ok());
       assert!(result.unwrap().access_token != session.access_token);
     });
   }
 
   #[test]
-  #[serial]
   fn refresh_fails_when_uuid_blank() {
     with_db(|db| {
-      let teachers_repository = TeachersRepository::new(&db);
-      let sessions_repository = SessionsRepository::new(&db);
-      let teacher = teachers_repository.create("john.doe@example.com".into(), "test".into()).unwrap();
-      let session = sessions_repository.create(&teacher).unwrap();
+      let teacher = TeacherFactory::new(&db).create();
+      let session = SessionFactory::new(&db, &teacher).create();
 
       assert_eq!(
-        refresh("".into(), session.refresh_token, &db),
+        refresh("".into(), session.refresh_token, &SessionLifetimes::default(), &db),
         Err(RefreshError::InvalidParams(vec![ValidationError::SessionUuidIsBlank])),
       );
     });
   }
 
   #[test]
-  #[serial]
   fn refresh_fails_when_refresh_token_blank() {
     with_db(|db| {
-      let teachers_repository = TeachersRepository::new(&db);
-      let sessions_repository = SessionsRepository::new(&db);
-      let teacher = teachers_repository.create("john.doe@example.com".into(), "test".into()).unwrap();
-      let session = sessions_repository.create(&teacher).unwrap();
+      let teacher = TeacherFactory::new(&db).create();
+      let session = SessionFactory::new(&db, &teacher).create();
 
       assert_eq!(
-        refresh(session.uuid, "".into(), &db),
+        refresh(session.uuid, "".into(), &SessionLifetimes::default(), &db),
         Err(RefreshError::InvalidParams(vec![ValidationError::RefreshTokenIsBlank])),
       );
     });
   }
 
   #[test]
-  #[serial]
   fn refresh_fails_when_session_doesnt_exist() {
     with_db(|db| {
       assert_eq!(
-        refresh("uuid".into(), "refresh_token".into(), &db),
+        refresh("uuid".into(), "refresh_token".into(), &SessionLifetimes::default(), &db),
         Err(RefreshError::SessionNotFound),
       );
     });
   }
 
   #[test]
-  #[serial]
   fn refresh_fails_when_refresh_token_doesnt_match() {
     with_db(|db| {
-      let teachers_repository = TeachersRepository::new(&db);
-      let sessions_repository = SessionsRepository::new(&db);
-      let teacher = teachers_repository.create("john.doe@example.com".into(), "test".into()).unwrap();
-      let session = sessions_repository.create(&teacher).unwrap();
+      let teacher = TeacherFactory::new(&db).create();
+      let session = SessionFactory::new(&db, &teacher).create();
 
       assert_eq!(
-        refresh(session.uuid, "invalid_refresh_token".into(), &db),
+        refresh(session.uuid, "invalid_refresh_token".into(), &SessionLifetimes::default(), &db),
         Err(RefreshError::Unauthorized),
       );
     });
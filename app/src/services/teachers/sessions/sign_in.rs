@@ -1,9 +1,10 @@
 use serde::{Serialize, Serializer};
 use db::prelude::*;
-use db::models::{Session, Teacher};
+use db::models::{Session, SessionLifetimes, Teacher};
+use tokio::task::spawn_blocking;
 
 use crate::utils::password;
-use crate::{report_unexpected_err, handle_unexpected_err, make_serializable};
+use crate::{report_unexpected_err, handle_unexpected_err, handle_unit_of_work, make_serializable};
 
 #[derive(PartialEq, Debug)]
 pub enum ValidationError {
@@ -11,13 +12,15 @@ pub enum ValidationError {
   PasswordIsBlank,
   TeacherNotFound,
   PasswordDoesntMatch,
+  TeacherIsDeactivated,
 }
 
 make_serializable!(ValidationError {
-  EmailIsBlank => "Email can't be blank",
-  PasswordIsBlank => "Password can't be blank",
-  TeacherNotFound => "Invalid email/password combination",
-  PasswordDoesntMatch => "Invalid email/password combination"
+  EmailIsBlank => ("email", "blank", "Email can't be blank"),
+  PasswordIsBlank => ("password", "blank", "Password can't be blank"),
+  TeacherNotFound => ("base", "invalid_credentials", "Invalid email/password combination"),
+  PasswordDoesntMatch => ("base", "invalid_credentials", "Invalid email/password combination"),
+  TeacherIsDeactivated => ("base", "deactivated", "This account has been deactivated"),
 });
 
 #[derive(PartialEq, Debug)]
@@ -26,110 +29,218 @@ pub enum SignInError {
   UnexpectedError,
 }
 
+fn validate_params(email: &str, password: &str) -> Result<(), SignInError> {
+  let mut errors = vec![];
+
+  if email.trim().is_empty() {
+    errors.push(ValidationError::EmailIsBlank);
+  }
+  if password.trim().is_empty() {
+    errors.push(ValidationError::PasswordIsBlank);
+  }
+
+  if errors.is_empty() {
+    Ok(())
+  } else {
+    Err(SignInError::InvalidParams(errors))
+  }
+}
+
+// Only checked after authentication, so that it doesn't reveal which emails are registered
+fn validate_teacher(teacher: &Teacher) -> Result<(), SignInError> {
+  if teacher.deactivated_at.is_none() {
+    Ok(())
+  } else {
+    Err(
+      SignInError::InvalidParams(
+        vec![ValidationError::TeacherIsDeactivated]
+      )
+    )
+  }
+}
+
+// The steps below are shared by `SignIn` and `AsyncSignIn`, which differ only in how they
+// query the database
+fn check_teacher(teacher: Result<Teacher, DbError>) -> Result<Teacher, SignInError> {
+  match teacher {
+    Ok(teacher) => Ok(teacher),
+    Err(DbError::RecordNotFound) => Err(
+      SignInError::InvalidParams(
+        vec![ValidationError::TeacherNotFound]
+      )
+    ),
+    Err(error) => handle_unexpected_err!(error, SignInError::UnexpectedError),
+  }
+}
+
+fn verify_password(password: &str, password_digest: &str) -> Result<(), SignInError> {
+  match password::verify(password, password_digest) {
+    Ok(true) => Ok(()),
+    Ok(false) => Err(
+      SignInError::InvalidParams(
+        vec![ValidationError::PasswordDoesntMatch]
+      )
+    ),
+    Err(error) => handle_unexpected_err!(error, SignInError::UnexpectedError),
+  }
+}
+
+fn check_session(session: Result<Session, DbError>) -> Result<Session, SignInError> {
+  match session {
+    Ok(session) => Ok(session),
+    Err(error) => handle_unexpected_err!(error, SignInError::UnexpectedError),
+  }
+}
+
 struct SignIn<'a> {
   pub email: String,
   pub password: String,
+  pub lifetimes: &'a SessionLifetimes,
   pub db: &'a DbConnection,
 }
 
 impl<'a> SignIn<'a> {
-  fn new(email: String, password: String, db: &'a DbConnection) -> Self {
+  fn new(
+    email: String,
+    password: String,
+    lifetimes: &'a SessionLifetimes,
+    db: &'a DbConnection,
+  ) -> Self {
     Self {
       email,
       password,
+      lifetimes,
       db,
     }
   }
 
-  fn validate_params(&self) -> Result<(), SignInError> {
-    let mut errors = vec![];
+  fn get_teacher(&self) -> Result<Teacher, SignInError> {
+    check_teacher(TeachersRepository::new(self.db).find_by_email(&self.email))
+  }
 
-    if self.email.trim().is_empty() {
-      errors.push(ValidationError::EmailIsBlank);
-    }
-    if self.password.trim().is_empty() {
-      errors.push(ValidationError::PasswordIsBlank);
-    }
+  fn authenticate(&self, teacher: &Teacher) -> Result<(), SignInError> {
+    verify_password(&self.password, &teacher.password_digest)
+  }
 
-    if errors.is_empty() {
-      Ok(())
-    } else {
-      Err(SignInError::InvalidParams(errors))
-    }
+  fn create_session(&self, teacher: &Teacher) -> Result<Session, SignInError> {
+    check_session(SessionsRepository::new(self.db).create(teacher, self.lifetimes))
   }
 
-  fn get_teacher(&self) -> Result<Teacher, SignInError> {
-    let repository = TeachersRepository::new(self.db);
-
-    match repository.find_by_email(&self.email) {
-      Ok(teacher) => Ok(teacher),
-      Err(DbError::RecordNotFound) => Err(
-        SignInError::InvalidParams(
-          vec![ValidationError::TeacherNotFound]
-        )
-      ),
-      Err(error) => handle_unexpected_err!(error, SignInError::UnexpectedError),
-    }
+  fn call(self) -> Result<Session, SignInError> {
+    validate_params(&self.email, &self.password)?;
+    let teacher = self.get_teacher()?;
+    self.authenticate(&teacher)?;
+    validate_teacher(&teacher)?;
+    let session = self.create_session(&teacher)?;
+
+    Ok(session)
   }
+}
 
-  fn authenticate(&self, teacher: &Teacher) -> Result<(), SignInError> {
-    match password::verify(&self.password, &teacher.password_digest) {
-      Ok(true) => Ok(()),
-      Ok(false) => Err(
-        SignInError::InvalidParams(
-          vec![ValidationError::PasswordDoesntMatch]
-        )
-      ),
-      Err(error) => handle_unexpected_err!(error, SignInError::UnexpectedError),
+pub fn sign_in(
+  email: String,
+  password: String,
+  lifetimes: &SessionLifetimes,
+  db: &DbConnection,
+) -> Result<Session, SignInError> {
+  let unit_of_work = UnitOfWork::run(db, |unit_of_work| {
+    SignIn::new(email, password, lifetimes, unit_of_work.connection()).call()
+  });
+
+  handle_unit_of_work!(unit_of_work, SignInError::UnexpectedError)
+}
+
+// Awaits the queries instead of blocking, so that it can be called without `web::block`.
+// Verifying the password is CPU-bound though, so it's moved to the blocking thread pool.
+struct AsyncSignIn<'a> {
+  pub email: String,
+  pub password: String,
+  pub lifetimes: &'a SessionLifetimes,
+  pub db: &'a dyn AsyncDbClient,
+}
+
+impl<'a> AsyncSignIn<'a> {
+  fn new(
+    email: String,
+    password: String,
+    lifetimes: &'a SessionLifetimes,
+    db: &'a dyn AsyncDbClient,
+  ) -> Self {
+    Self {
+      email,
+      password,
+      lifetimes,
+      db,
     }
   }
 
-  fn create_session(&self, teacher: &Teacher) -> Result<Session, SignInError> {
-    let repository = SessionsRepository::new(self.db);
+  async fn get_teacher(&self) -> Result<Teacher, SignInError> {
+    check_teacher(AsyncTeachersRepository::new(self.db).find_by_email(&self.email).await)
+  }
+
+  async fn authenticate(&self, teacher: &Teacher) -> Result<(), SignInError> {
+    let password = self.password.clone();
+    let password_digest = teacher.password_digest.clone();
 
-    match repository.create(teacher) {
-      Ok(session) => Ok(session),
+    match spawn_blocking(move || verify_password(&password, &password_digest)).await {
+      Ok(verification) => verification,
       Err(error) => handle_unexpected_err!(error, SignInError::UnexpectedError),
     }
   }
 
-  fn call(self) -> Result<Session, SignInError> {
-    self.validate_params()?;
-    let teacher = self.get_teacher()?;
-    self.authenticate(&teacher)?;
-    let session = self.create_session(&teacher)?;
+  async fn create_session(&self, teacher: &Teacher) -> Result<Session, SignInError> {
+    check_session(AsyncSessionsRepository::new(self.db).create(teacher, self.lifetimes).await)
+  }
+
+  async fn call(self) -> Result<Session, SignInError> {
+    validate_params(&self.email, &self.password)?;
+    let teacher = self.get_teacher().await?;
+    self.authenticate(&teacher).await?;
+    validate_teacher(&teacher)?;
+    let session = self.create_session(&teacher).await?;
 
     Ok(session)
   }
 }
 
-pub fn sign_in(email: String, password: String, db: &DbConnection) -> Result<Session, SignInError> {
-  SignIn::new(email, password, db).call()
+pub async fn sign_in_async(
+  email: String,
+  password: String,
+  lifetimes: &SessionLifetimes,
+  db: &mut AsyncDbConnection,
+) -> Result<Session, SignInError> {
+  let unit_of_work = match AsyncUnitOfWork::begin(db).await {
+    Ok(unit_of_work) => unit_of_work,
+    Err(error) => return handle_unexpected_err!(error, SignInError::UnexpectedError),
+  };
+  let session = AsyncSignIn::new(email, password, lifetimes, unit_of_work.connection())
+    .call()
+    .await;
+
+  handle_unit_of_work!(unit_of_work.finish(session).await, SignInError::UnexpectedError)
 }
 
 #[cfg(test)]
 mod tests {
-  use serial_test::serial;
-  use db::utils::test::with_db;
+  use db::utils::test::{with_async_db, with_db};
+  use db::utils::test::factories::unique_email;
   use super::*;
 
   #[test]
-  #[serial]
   fn sign_in_works() {
     with_db(|db| {
       let teachers_repository = TeachersRepository::new(&db);
       let sessions_repository = SessionsRepository::new(&db);
-      let email = "john.doe@example.com".to_string();
+      let email = unique_email();
       let password = "password".to_string();
       teachers_repository.create(email.clone(), password::digest(&password).unwrap()).unwrap();
 
-      assert!(sign_in(email, password, &db).is_ok());
+      assert!(sign_in(email, password, &SessionLifetimes::default(), &db).is_ok());
       assert_eq!(sessions_repository.count().unwrap(), 1);
     })
   }
 
   #[test]
-  #[serial]
   fn sign_in_fails_when_email_is_blank() {
     with_db(|db| {
       let sessions_repository = SessionsRepository::new(&db);
@@ -137,7 +248,7 @@ mod tests {
       let password = "password".to_string();
 
       assert_eq!(
-        sign_in(email, password, &db),
+        sign_in(email, password, &SessionLifetimes::default(), &db),
         Err(SignInError::InvalidParams(vec![ValidationError::EmailIsBlank]))
       );
       assert_eq!(sessions_repository.count().unwrap(), 0);
@@ -145,15 +256,14 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn sign_in_fails_when_password_is_blank() {
     with_db(|db| {
       let sessions_repository = SessionsRepository::new(&db);
-      let email = "john.doe@example.com".to_string();
+      let email = unique_email();
       let password = "".to_string();
 
       assert_eq!(
-        sign_in(email, password, &db),
+        sign_in(email, password, &SessionLifetimes::default(), &db),
         Err(SignInError::InvalidParams(vec![ValidationError::PasswordIsBlank]))
       );
       assert_eq!(sessions_repository.count().unwrap(), 0);
@@ -161,15 +271,14 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn sign_in_fails_when_teacher_doesnt_exist() {
     with_db(|db| {
       let sessions_repository = SessionsRepository::new(&db);
-      let email = "john.doe@example.com".to_string();
+      let email = unique_email();
       let password = "password".to_string();
 
       assert_eq!(
-        sign_in(email, password, &db),
+        sign_in(email, password, &SessionLifetimes::default(), &db),
         Err(SignInError::InvalidParams(vec![ValidationError::TeacherNotFound]))
       );
       assert_eq!(sessions_repository.count().unwrap(), 0);
@@ -177,21 +286,109 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn sign_in_fails_when_password_doesnt_match() {
     with_db(|db| {
       let sessions_repository = SessionsRepository::new(&db);
       let teachers_repository = TeachersRepository::new(&db);
-      let email = "john.doe@example.com".to_string();
+      let email = unique_email();
       let password = "password".to_string();
       let invalid_password = "invalid_password".to_string();
       teachers_repository.create(email.clone(), password::digest(&password).unwrap()).unwrap();
 
       assert_eq!(
-        sign_in(email, invalid_password, &db),
+        sign_in(email, invalid_password, &SessionLifetimes::default(), &db),
         Err(SignInError::InvalidParams(vec![ValidationError::PasswordDoesntMatch]))
       );
       assert_eq!(sessions_repository.count().unwrap(), 0);
     })
   }
+
+  #[test]
+  fn sign_in_fails_when_teacher_is_deactivated() {
+    with_db(|db| {
+      let sessions_repository = SessionsRepository::new(&db);
+      let teachers_repository = TeachersRepository::new(&db);
+      let email = unique_email();
+      let password = "password".to_string();
+      let teacher = teachers_repository
+        .create(email.clone(), password::digest(&password).unwrap())
+        .unwrap();
+      teachers_repository.deactivate(&teacher).unwrap();
+
+      assert_eq!(
+        sign_in(email, password, &SessionLifetimes::default(), &db),
+        Err(SignInError::InvalidParams(vec![ValidationError::TeacherIsDeactivated]))
+      );
+      assert_eq!(sessions_repository.count().unwrap(), 0);
+    })
+  }
+
+  #[test]
+  fn sign_in_async_works() {
+    with_async_db(|mut async_db, db| async move {
+      let teachers_repository = TeachersRepository::new(&db);
+      let sessions_repository = SessionsRepository::new(&db);
+      let email = unique_email();
+      let password = "password".to_string();
+      teachers_repository.create(email.clone(), password::digest(&password).unwrap()).unwrap();
+
+      let result = sign_in_async(email, password, &SessionLifetimes::default(), &mut async_db).await;
+
+      assert!(result.is_ok());
+      assert_eq!(sessions_repository.count().unwrap(), 1);
+    })
+  }
+
+  #[test]
+  fn sign_in_async_fails_when_teacher_doesnt_exist() {
+    with_async_db(|mut async_db, db| async move {
+      let sessions_repository = SessionsRepository::new(&db);
+      let email = unique_email();
+      let password = "password".to_string();
+
+      assert_eq!(
+        sign_in_async(email, password, &SessionLifetimes::default(), &mut async_db).await,
+        Err(SignInError::InvalidParams(vec![ValidationError::TeacherNotFound]))
+      );
+      assert_eq!(sessions_repository.count().unwrap(), 0);
+    })
+  }
+
+  #[test]
+  fn sign_in_async_fails_when_password_doesnt_match() {
+    with_async_db(|mut async_db, db| async move {
+      let sessions_repository = SessionsRepository::new(&db);
+      let teachers_repository = TeachersRepository::new(&db);
+      let email = unique_email();
+      let password = "password".to_string();
+      let invalid_password = "invalid_password".to_string();
+      teachers_repository.create(email.clone(), password::digest(&password).unwrap()).unwrap();
+
+      assert_eq!(
+        sign_in_async(email, invalid_password, &SessionLifetimes::default(), &mut async_db).await,
+        Err(SignInError::InvalidParams(vec![ValidationError::PasswordDoesntMatch]))
+      );
+      assert_eq!(sessions_repository.count().unwrap(), 0);
+    })
+  }
+
+  #[test]
+  fn sign_in_async_fails_when_teacher_is_deactivated() {
+    with_async_db(|mut async_db, db| async move {
+      let sessions_repository = SessionsRepository::new(&db);
+      let teachers_repository = TeachersRepository::new(&db);
+      let email = unique_email();
+      let password = "password".to_string();
+      let teacher = teachers_repository
+        .create(email.clone(), password::digest(&password).unwrap())
+        .unwrap();
+      teachers_repository.deactivate(&teacher).unwrap();
+
+      assert_eq!(
+        sign_in_async(email, password, &SessionLifetimes::default(), &mut async_db).await,
+        Err(SignInError::InvalidParams(vec![ValidationError::TeacherIsDeactivated]))
+      );
+      assert_eq!(sessions_repository.count().unwrap(), 0);
+    })
+  }
 }
@@ -11,8 +11,8 @@ pub enum ValidationError {
 }
 
 make_serializable!(ValidationError {
-  SessionUuidIsBlank => "Session UUID can't be blank",
-  RefreshTokenIsBlank => "Refresh token can't be blank"
+  SessionUuidIsBlank => ("session_uuid", "blank", "Session UUID can't be blank"),
+  RefreshTokenIsBlank => ("refresh_token", "blank", "Refresh token can't be blank"),
 });
 
 #[derive(PartialEq, Debug)]
@@ -94,18 +94,16 @@ pub fn sign_out(session_uuid: String, refresh_token: String, db: &DbConnection)
 
 #[cfg(test)]
 mod tests {
-  use serial_test::serial;
   use db::utils::test::with_db;
+  use db::utils::test::factories::{SessionFactory, TeacherFactory};
   use super::*;
 
   #[test]
-  #[serial]
   fn sign_out_works() {
     with_db(|db| {
-      let teachers_repository = TeachersRepository::new(&db);
       let sessions_repository = SessionsRepository::new(&db);
-      let teacher = teachers_repository.create("john.doe@example.com".into(), "test".into()).unwrap();
-      let session = sessions_repository.create(&teacher).unwrap();
+      let teacher = TeacherFactory::new(&db).create();
+      let session = SessionFactory::new(&db, &teacher).create();
 
       let result = sign_out(session.uuid, session.refresh_token, &db);
       assert!(result.is_ok());
@@ -114,13 +112,11 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn sign_out_fails_when_uuid_blank() {
     with_db(|db| {
-      let teachers_repository = TeachersRepository::new(&db);
       let sessions_repository = SessionsRepository::new(&db);
-      let teacher = teachers_repository.create("john.doe@example.com".into(), "test".into()).unwrap();
-      let session = sessions_repository.create(&teacher).unwrap();
+      let teacher = TeacherFactory::new(&db).create();
+      let session = SessionFactory::new(&db, &teacher).create();
 
       assert_eq!(
         sign_out("".into(), session.refresh_token, &db),
@@ -131,13 +127,11 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn sign_out_fails_when_refresh_token_blank() {
     with_db(|db| {
-      let teachers_repository = TeachersRepository::new(&db);
       let sessions_repository = SessionsRepository::new(&db);
-      let teacher = teachers_repository.create("john.doe@example.com".into(), "test".into()).unwrap();
-      let session = sessions_repository.create(&teacher).unwrap();
+      let teacher = TeacherFactory::new(&db).create();
+      let session = SessionFactory::new(&db, &teacher).create();
 
       assert_eq!(
         sign_out(session.uuid, "".into(), &db),
@@ -148,7 +142,6 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn sign_out_fails_when_session_doesnt_exist() {
     with_db(|db| {
       assert_eq!(
@@ -159,13 +152,11 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn sign_out_fails_when_refresh_token_doesnt_match() {
     with_db(|db| {
-      let teachers_repository = TeachersRepository::new(&db);
       let sessions_repository = SessionsRepository::new(&db);
-      let teacher = teachers_repository.create("john.doe@example.com".into(), "test".into()).unwrap();
-      let session = sessions_repository.create(&teacher).unwrap();
+      let teacher = TeacherFactory::new(&db).create();
+      let session = SessionFactory::new(&db, &teacher).create();
 
       assert_eq!(
         sign_out(session.uuid, "invalid_refresh_token".into(), &db),
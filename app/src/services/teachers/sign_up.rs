@@ -17,12 +17,14 @@ pub enum ValidationError {
 }
 
 make_serializable!(ValidationError {
-  EmailIsBlank => "Email can't be blank",
-  EmailIsInvalid => "Email is invalid",
-  PasswordIsBlank => "Password can't be blank",
+  EmailIsBlank => ("email", "blank", "Email can't be blank"),
+  EmailIsInvalid => ("email", "invalid", "Email is invalid"),
+  PasswordIsBlank => ("password", "blank", "Password can't be blank"),
   // TODO: Give better feedback on password security
-  PasswordIsTooShort => "Password is too short (minimum is 8 characters)",
-  PasswordIsTooLong => "Password is too long (maximum is 128 characters)",
+  PasswordIsTooShort =>
+    ("password", "too_short", "Password is too short (minimum is 8 characters)"),
+  PasswordIsTooLong =>
+    ("password", "too_long", "Password is too long (maximum is 128 characters)"),
 });
 
 #[derive(PartialEq, Debug)]
@@ -74,7 +76,6 @@ impl<'a> SignUp<'a> {
   }
 
   fn create_teacher(&self) -> Result<(), SignUpError> {
-    let repository = TeachersRepository::new(self.db);
     let password_digest = password::digest(&self.password)
       .map_err(|error| {
         // Report unexpected errors from argon2
@@ -82,13 +83,21 @@ impl<'a> SignUp<'a> {
         SignUpError::UnexpectedError
       })?;
 
-    match repository.create(self.email.clone(), password_digest) {
+    // The insert gets its own savepoint, so that a failed one doesn't abort the surrounding
+    // transaction
+    let unit_of_work = UnitOfWork::run(self.db, |unit_of_work| {
+      unit_of_work.repository::<TeachersRepository>().create(self.email.clone(), password_digest)
+    });
+
+    match unit_of_work {
       Ok(_) => Ok(()),
       // If the email is already taken we still want to pretend that the sign up
       // was successful - this is a security measure against user enumeration
       // https://blog.rapid7.com/2017/06/15/about-user-enumeration
-      Err(DbError::UniqueConstraintViolation(_)) => Ok(()),
-      Err(error) => handle_unexpected_err!(error, SignUpError::UnexpectedError),
+      Err(UnitOfWorkError::Aborted(DbError::UniqueConstraintViolation(_))) => Ok(()),
+      Err(UnitOfWorkError::Aborted(error)) | Err(UnitOfWorkError::DbError(error)) => {
+        handle_unexpected_err!(error, SignUpError::UnexpectedError)
+      },
     }
   }
 
@@ -106,16 +115,15 @@ pub fn sign_up(email: String, password: String, db: &DbConnection) -> Result<(),
 
 #[cfg(test)]
 mod tests {
-  use serial_test::serial;
   use db::utils::test::with_db;
+  use db::utils::test::factories::unique_email;
   use super::*;
 
   #[test]
-  #[serial]
   fn sign_up_works() {
     with_db(|db| {
       let repository = TeachersRepository::new(&db);
-      let email = "john.doe@example.com".to_string();
+      let email = unique_email();
       let password = "password".to_string();
 
       assert!(sign_up(email, password, &db).is_ok());
@@ -124,11 +132,10 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn sign_up_works_when_user_already_exists() {
     with_db(|db| {
       let repository = TeachersRepository::new(&db);
-      let email = "john.doe@example.com".to_string();
+      let email = unique_email();
       let password = "password".to_string();
       repository.create(email.clone(), "test".into()).unwrap();
 
@@ -138,7 +145,6 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn sign_up_fails_when_email_is_blank() {
     with_db(|db| {
       let repository = TeachersRepository::new(&db);
@@ -154,7 +160,6 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn sign_up_fails_when_email_is_invalid() {
     with_db(|db| {
       let repository = TeachersRepository::new(&db);
@@ -170,11 +175,10 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn sign_up_fails_when_password_is_blank() {
     with_db(|db| {
       let repository = TeachersRepository::new(&db);
-      let email = "john.doe@example.com".to_string();
+      let email = unique_email();
       let password = "".to_string();
 
       assert_eq!(
@@ -186,11 +190,10 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn sign_up_fails_when_password_is_too_short() {
     with_db(|db| {
       let repository = TeachersRepository::new(&db);
-      let email = "john.doe@example.com".to_string();
+      let email = unique_email();
       let password = "qwe".to_string();
 
       assert_eq!(
@@ -202,11 +205,10 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn sign_up_fails_when_password_is_too_long() {
     with_db(|db| {
       let repository = TeachersRepository::new(&db);
-      let email = "john.doe@example.com".to_string();
+      let email = unique_email();
       let password: String = ['a'; 129].iter().collect();
 
       assert_eq!(
@@ -216,4 +218,14 @@ mod tests {
       assert_eq!(repository.count().unwrap(), 0);
     })
   }
+
+  #[test]
+  fn validation_errors_are_scoped_to_fields() {
+    use crate::utils::errors::FieldError;
+
+    let error = ValidationError::PasswordIsTooShort;
+    assert_eq!(error.field(), "password");
+    assert_eq!(error.code(), "too_short");
+    assert_eq!(error.to_string(), "Password is too short (minimum is 8 characters)");
+  }
 }
@@ -0,0 +1,10 @@
+// Implemented for every service's `ValidationError` by `make_serializable!`, so that the callers
+// (e.g. the web crate) can tell which param an error refers to without matching on each enum.
+pub trait FieldError: ToString {
+  // Name of the offending param, or "base" when the error isn't tied to a single one
+  fn field(&self) -> &'static str;
+  // Stable, machine-readable identifier of the error, e.g. "blank" or "too_short"
+  fn code(&self) -> &'static str;
+  // Every variant of the error, e.g. for checking that each of them has a translation
+  fn variants() -> Vec<Self> where Self: Sized;
+}
@@ -7,7 +7,10 @@ macro_rules! report_unexpected_err {
 
     let err = $err; // "report_error!" doesn't accept "$err" so we need to bind it first.
     error!("{:?}", err); // Log error first, as "report_error!" consumes the binding
-    #[cfg(not(test))] report_error!(ROLLBAR_CLIENT, err); // Start a thread for uploading the error to Rollbar
+    #[cfg(not(test))]
+    if let Some(client) = ROLLBAR_CLIENT.get() {
+      report_error!(client, err); // Start a thread for uploading the error to Rollbar
+    }
   }};
 }
 
@@ -21,9 +24,59 @@ macro_rules! handle_unexpected_err {
   }};
 }
 
+// Passes the errors of the service run in a unit of work through. The errors of beginning or
+// finishing the transaction itself are handled like the other unexpected ones.
+#[macro_export]
+macro_rules! handle_unit_of_work {
+  ($unit_of_work:expr, $result:expr) => {{
+    use db::prelude::UnitOfWorkError;
+    use crate::handle_unexpected_err;
+
+    match $unit_of_work {
+      Ok(value) => Ok(value),
+      Err(UnitOfWorkError::Aborted(error)) => Err(error),
+      Err(UnitOfWorkError::DbError(error)) => handle_unexpected_err!(error, $result),
+    }
+  }};
+}
+
+// Maps the errors of authenticating the teacher onto the same variants of the service's error
+#[macro_export]
+macro_rules! handle_authentication {
+  ($authentication:expr, $error:ident) => {{
+    use $crate::services::teachers::AuthenticationError;
+
+    match $authentication {
+      Ok(teacher) => Ok(teacher),
+      Err(AuthenticationError::Unauthorized) => Err($error::Unauthorized),
+      Err(AuthenticationError::UnexpectedError) => Err($error::UnexpectedError),
+    }
+  }};
+}
+
 #[macro_export]
 macro_rules! make_serializable {
-  ($err_type:ty { $($err_variant:ident => $err_description:expr),+ $(,)? }) => {
+  ($err_type:ty {
+    $($err_variant:ident => ($err_field:expr, $err_code:expr, $err_description:expr)),+ $(,)?
+  }) => {
+    impl $crate::utils::errors::FieldError for $err_type {
+      fn field(&self) -> &'static str {
+        match self {
+          $( Self::$err_variant => $err_field, )+
+        }
+      }
+
+      fn code(&self) -> &'static str {
+        match self {
+          $( Self::$err_variant => $err_code, )+
+        }
+      }
+
+      fn variants() -> Vec<Self> {
+        vec![$( Self::$err_variant, )+]
+      }
+    }
+
     impl ToString for $err_type {
       fn to_string(&self) -> String {
         match self {
@@ -34,7 +87,14 @@ macro_rules! make_serializable {
 
     impl Serialize for $err_type {
       fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
-        serializer.serialize_str(&self.to_string())
+        use serde::ser::SerializeStruct;
+        use $crate::utils::errors::FieldError;
+
+        let mut error = serializer.serialize_struct(stringify!($err_type), 3)?;
+        error.serialize_field("field", self.field())?;
+        error.serialize_field("code", self.code())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.end()
       }
     }
   }
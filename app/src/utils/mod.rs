@@ -1,3 +1,5 @@
 pub mod constants;
+pub mod errors;
 pub mod macros;
 pub mod password;
+pub mod rollbar;
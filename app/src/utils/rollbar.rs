@@ -0,0 +1,9 @@
+use once_cell::sync::OnceCell;
+
+// Set up once at boot. Without it (e.g. in development) unexpected errors are only logged.
+pub static ROLLBAR_CLIENT: OnceCell<rollbar::Client> = OnceCell::new();
+
+pub fn configure_rollbar(access_token: String, environment: String) {
+  // The client can't be replaced once it has been used, so only the first call counts
+  ROLLBAR_CLIENT.get_or_init(|| rollbar::Client::new(access_token, environment));
+}
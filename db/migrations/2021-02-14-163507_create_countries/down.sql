@@ -0,0 +1,4 @@
+DROP INDEX countries_unique_alpha3_code;
+DROP INDEX countries_unique_alpha2_code;
+DROP INDEX countries_continent;
+DROP TABLE countries;
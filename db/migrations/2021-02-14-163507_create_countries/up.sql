@@ -0,0 +1,19 @@
+CREATE TABLE countries (
+  id SERIAL PRIMARY KEY,
+  alpha2_code VARCHAR NOT NULL,
+  alpha3_code VARCHAR NOT NULL,
+  name_pl VARCHAR NOT NULL,
+  name_en VARCHAR NOT NULL,
+  capital_pl VARCHAR NOT NULL,
+  capital_en VARCHAR NOT NULL,
+  continent VARCHAR NOT NULL,
+  neighbours VARCHAR[] NOT NULL DEFAULT '{}',
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX countries_continent ON countries(continent);
+CREATE UNIQUE INDEX countries_unique_alpha2_code ON countries(alpha2_code);
+CREATE UNIQUE INDEX countries_unique_alpha3_code ON countries(alpha3_code);
+
+SELECT diesel_manage_updated_at('countries');
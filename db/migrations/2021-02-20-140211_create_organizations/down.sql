@@ -0,0 +1,2 @@
+DROP INDEX organizations_unique_uuid;
+DROP TABLE organizations;
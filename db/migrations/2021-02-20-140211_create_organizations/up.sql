@@ -0,0 +1,11 @@
+CREATE TABLE organizations (
+  id SERIAL PRIMARY KEY,
+  uuid VARCHAR NOT NULL,
+  name VARCHAR NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX organizations_unique_uuid ON organizations(uuid);
+
+SELECT diesel_manage_updated_at('organizations');
@@ -0,0 +1,6 @@
+DROP INDEX teachers_organization_id;
+
+ALTER TABLE teachers
+  DROP COLUMN deactivated_at,
+  DROP COLUMN organization_role,
+  DROP COLUMN organization_id;
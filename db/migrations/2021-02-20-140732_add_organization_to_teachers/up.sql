@@ -0,0 +1,6 @@
+ALTER TABLE teachers
+  ADD COLUMN organization_id INTEGER REFERENCES organizations(id),
+  ADD COLUMN organization_role VARCHAR,
+  ADD COLUMN deactivated_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX teachers_organization_id ON teachers(organization_id);
@@ -0,0 +1,4 @@
+DROP INDEX organization_invitations_unique_token;
+DROP INDEX organization_invitations_unique_uuid;
+DROP INDEX organization_invitations_organization_id;
+DROP TABLE organization_invitations;
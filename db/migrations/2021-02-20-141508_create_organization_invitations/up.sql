@@ -0,0 +1,18 @@
+CREATE TABLE organization_invitations (
+  id SERIAL PRIMARY KEY,
+  uuid VARCHAR NOT NULL,
+  organization_id INTEGER NOT NULL REFERENCES organizations(id),
+  email VARCHAR NOT NULL,
+  role VARCHAR NOT NULL,
+  token VARCHAR NOT NULL,
+  expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+  accepted_at TIMESTAMP WITH TIME ZONE,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX organization_invitations_organization_id ON organization_invitations(organization_id);
+CREATE UNIQUE INDEX organization_invitations_unique_uuid ON organization_invitations(uuid);
+CREATE UNIQUE INDEX organization_invitations_unique_token ON organization_invitations(token);
+
+SELECT diesel_manage_updated_at('organization_invitations');
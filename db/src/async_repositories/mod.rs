@@ -0,0 +1,11 @@
+// Counterparts of the `repositories` which don't block the calling thread, so that the web
+// handlers can await them directly. Queries are written by hand, as Diesel is synchronous.
+// Only the queries of the sign-in have them, the other handlers run the synchronous services
+// with `web::block`.
+mod repository;
+mod teachers_repository;
+mod sessions_repository;
+
+pub use teachers_repository::AsyncTeachersRepository;
+pub use sessions_repository::AsyncSessionsRepository;
+pub use repository::AsyncDbClient;
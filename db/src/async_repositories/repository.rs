@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use tokio_postgres::{types::ToSql, Error, Row, Statement};
+
+use crate::utils::types::AsyncDbConnection;
+
+// Implemented by the pooled connections and by their transactions, so that the repositories
+// can be used inside of an `AsyncUnitOfWork` as well. Statements are cached by both of them.
+#[async_trait]
+pub trait AsyncDbClient: Sync {
+  async fn prepare(&self, query: &str) -> Result<Statement, Error>;
+
+  async fn query_one(
+    &self,
+    statement: &Statement,
+    params: &[&(dyn ToSql + Sync)],
+  ) -> Result<Row, Error>;
+
+  async fn query_opt(
+    &self,
+    statement: &Statement,
+    params: &[&(dyn ToSql + Sync)],
+  ) -> Result<Option<Row>, Error>;
+}
+
+#[async_trait]
+impl AsyncDbClient for AsyncDbConnection {
+  async fn prepare(&self, query: &str) -> Result<Statement, Error> {
+    deadpool_postgres::ClientWrapper::prepare(self, query).await
+  }
+
+  async fn query_one(
+    &self,
+    statement: &Statement,
+    params: &[&(dyn ToSql + Sync)],
+  ) -> Result<Row, Error> {
+    tokio_postgres::Client::query_one(self, statement, params).await
+  }
+
+  async fn query_opt(
+    &self,
+    statement: &Statement,
+    params: &[&(dyn ToSql + Sync)],
+  ) -> Result<Option<Row>, Error> {
+    tokio_postgres::Client::query_opt(self, statement, params).await
+  }
+}
+
+#[async_trait]
+impl<'t> AsyncDbClient for deadpool_postgres::Transaction<'t> {
+  async fn prepare(&self, query: &str) -> Result<Statement, Error> {
+    deadpool_postgres::Transaction::prepare(self, query).await
+  }
+
+  async fn query_one(
+    &self,
+    statement: &Statement,
+    params: &[&(dyn ToSql + Sync)],
+  ) -> Result<Row, Error> {
+    tokio_postgres::Transaction::query_one(self, statement, params).await
+  }
+
+  async fn query_opt(
+    &self,
+    statement: &Statement,
+    params: &[&(dyn ToSql + Sync)],
+  ) -> Result<Option<Row>, Error> {
+    tokio_postgres::Transaction::query_opt(self, statement, params).await
+  }
+}
@@ -0,0 +1,90 @@
+use tokio_postgres::Row;
+
+use crate::utils::errors::DbError;
+use crate::models::Teacher;
+use crate::models::session::{Session, SessionLifetimes, NewTeacherSession};
+use crate::async_repositories::AsyncDbClient;
+use crate::repositories::Repository;
+
+pub struct AsyncSessionsRepository<'a> {
+  db: &'a dyn AsyncDbClient,
+}
+
+impl<'a> Repository<'a> for AsyncSessionsRepository<'a> {
+  type Db = dyn AsyncDbClient + 'a;
+
+  fn new(db: &'a Self::Db) -> Self {
+    Self { db }
+  }
+}
+
+impl<'a> AsyncSessionsRepository<'a> {
+  pub async fn create(
+    &self,
+    teacher: &Teacher,
+    lifetimes: &SessionLifetimes,
+  ) -> Result<Session, DbError> {
+    let new_session = NewTeacherSession::new(teacher.uuid.clone(), lifetimes);
+    let statement = self.db.prepare(
+      "INSERT INTO sessions (
+        uuid,
+        owner_type,
+        owner_uuid,
+        refresh_token,
+        refresh_token_expires_at,
+        access_token,
+        access_token_expires_at
+      ) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *"
+    ).await?;
+
+    let row = self.db.query_one(&statement, &[
+      &new_session.uuid,
+      &new_session.owner_type,
+      &new_session.owner_uuid,
+      &new_session.refresh_token,
+      &new_session.refresh_token_expires_at,
+      &new_session.access_token,
+      &new_session.access_token_expires_at,
+    ]).await?;
+
+    Ok(to_session(&row))
+  }
+}
+
+fn to_session(row: &Row) -> Session {
+  Session {
+    id: row.get("id"),
+    uuid: row.get("uuid"),
+    owner_type: row.get("owner_type"),
+    owner_uuid: row.get("owner_uuid"),
+    refresh_token: row.get("refresh_token"),
+    refresh_token_expires_at: row.get("refresh_token_expires_at"),
+    access_token: row.get("access_token"),
+    access_token_expires_at: row.get("access_token_expires_at"),
+    created_at: row.get("created_at"),
+    updated_at: row.get("updated_at"),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::repositories::{Repository, SessionsRepository};
+  use crate::utils::test::factories::TeacherFactory;
+  use crate::utils::test::with_async_db;
+  use super::*;
+
+  #[test]
+  fn create_works() {
+    with_async_db(|db, connection| async move {
+      let teacher = TeacherFactory::new(&connection).create();
+
+      let session = AsyncSessionsRepository::new(&db).create(&teacher, &SessionLifetimes::default()).await;
+      assert!(session.is_ok());
+      assert_eq!(session.as_ref().unwrap().owner_uuid, teacher.uuid);
+      assert_eq!(
+        SessionsRepository::new(&connection).find_by_uuid(&session.as_ref().unwrap().uuid),
+        session,
+      );
+    })
+  }
+}
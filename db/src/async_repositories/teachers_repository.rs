@@ -0,0 +1,71 @@
+use tokio_postgres::Row;
+
+use crate::utils::errors::DbError;
+use crate::models::Teacher;
+use crate::async_repositories::AsyncDbClient;
+use crate::repositories::Repository;
+
+pub struct AsyncTeachersRepository<'a> {
+  db: &'a dyn AsyncDbClient,
+}
+
+impl<'a> Repository<'a> for AsyncTeachersRepository<'a> {
+  type Db = dyn AsyncDbClient + 'a;
+
+  fn new(db: &'a Self::Db) -> Self {
+    Self { db }
+  }
+}
+
+impl<'a> AsyncTeachersRepository<'a> {
+  pub async fn find_by_email(&self, teacher_email: &str) -> Result<Teacher, DbError> {
+    // Statements are cached per connection by the pool
+    let statement = self.db.prepare("SELECT * FROM teachers WHERE email = $1 LIMIT 1").await?;
+
+    match self.db.query_opt(&statement, &[&teacher_email]).await? {
+      Some(row) => Ok(to_teacher(&row)),
+      None => Err(DbError::RecordNotFound),
+    }
+  }
+}
+
+fn to_teacher(row: &Row) -> Teacher {
+  Teacher {
+    id: row.get("id"),
+    uuid: row.get("uuid"),
+    email: row.get("email"),
+    password_digest: row.get("password_digest"),
+    created_at: row.get("created_at"),
+    updated_at: row.get("updated_at"),
+    organization_id: row.get("organization_id"),
+    organization_role: row.get("organization_role"),
+    deactivated_at: row.get("deactivated_at"),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::utils::test::factories::{unique_email, TeacherFactory};
+  use crate::utils::test::with_async_db;
+  use super::*;
+
+  #[test]
+  fn find_by_email_works() {
+    with_async_db(|db, connection| async move {
+      let teacher = TeacherFactory::new(&connection).create();
+
+      let found_teacher = AsyncTeachersRepository::new(&db).find_by_email(&teacher.email).await;
+      assert_eq!(found_teacher, Ok(teacher));
+    })
+  }
+
+  #[test]
+  fn find_by_email_fails_when_teacher_doesnt_exist() {
+    with_async_db(|db, _| async move {
+      assert_eq!(
+        AsyncTeachersRepository::new(&db).find_by_email(&unique_email()).await,
+        Err(DbError::RecordNotFound),
+      );
+    })
+  }
+}
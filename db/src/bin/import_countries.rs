@@ -0,0 +1,31 @@
+use db::prelude::*;
+use db::seeds;
+
+fn import() -> Result<usize, String> {
+  let database_url = std::env::var("DATABASE_URL")
+    .map_err(|_| String::from("DATABASE_URL is not set!"))?;
+  run_migrations(&database_url)?;
+  let pool = create_database_connection_pool(
+    &database_url,
+    &PoolSettings::default(),
+    &PoolMetrics::default(),
+  )?;
+  let connection = pool.get()
+    .map_err(|err| format!("Failed to establish database connection: {}", err))?;
+
+  seeds::countries::import(&connection)
+}
+
+fn main() {
+  dotenv::dotenv().ok();
+
+  println!("* Importing countries..");
+
+  match import() {
+    Ok(count) => println!("* Imported {} countries", count),
+    Err(error) => {
+      eprintln!("{}", error);
+      std::process::exit(1);
+    }
+  }
+}
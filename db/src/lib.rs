@@ -9,11 +9,34 @@ mod schema;
 pub mod utils;
 pub mod models;
 pub mod repositories;
+pub mod async_repositories;
+pub mod seeds;
 
 pub mod prelude {
-  pub use crate::utils::types::{DbPool, DbConnection};
+  pub use crate::utils::types::{AsyncDbConnection, AsyncDbPool, DbPool, DbConnection};
   pub use crate::utils::errors::DbError;
-  pub use crate::utils::connection_pool::create_database_connection_pool;
+  pub use crate::utils::connection_pool::{
+    async_database_pool_status,
+    check_database_connection,
+    create_async_database_connection_pool,
+    create_database_connection_pool,
+    database_pool_status,
+    PoolMetrics,
+    PoolSettings,
+  };
   pub use crate::utils::migrations::run_migrations;
-  pub use crate::repositories::{Repository, SessionsRepository, TeachersRepository};
+  pub use crate::utils::unit_of_work::{AsyncUnitOfWork, UnitOfWork, UnitOfWorkError};
+  pub use crate::repositories::{
+    CountriesRepository,
+    OrganizationInvitationsRepository,
+    OrganizationsRepository,
+    Repository,
+    SessionsRepository,
+    TeachersRepository,
+  };
+  pub use crate::async_repositories::{
+    AsyncDbClient,
+    AsyncSessionsRepository,
+    AsyncTeachersRepository,
+  };
 }
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+
+use crate::schema::countries;
+
+pub const CONTINENTS: [&str; 6] = [
+  "africa",
+  "asia",
+  "europe",
+  "north_america",
+  "oceania",
+  "south_america",
+];
+
+#[derive(PartialEq, Identifiable, Queryable, Debug)]
+#[table_name = "countries"]
+pub struct Country {
+  pub id: i32,
+  pub alpha2_code: String,
+  pub alpha3_code: String,
+  pub name_pl: String,
+  pub name_en: String,
+  pub capital_pl: String,
+  pub capital_en: String,
+  pub continent: String,
+  pub neighbours: Vec<String>,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Clone, Debug)]
+#[table_name = "countries"]
+pub struct NewCountry {
+  pub alpha2_code: String,
+  pub alpha3_code: String,
+  pub name_pl: String,
+  pub name_en: String,
+  pub capital_pl: String,
+  pub capital_en: String,
+  pub continent: String,
+  pub neighbours: Vec<String>,
+}
@@ -1,5 +1,11 @@
+pub mod country;
+pub mod organization;
+pub mod organization_invitation;
 pub mod session;
 pub mod teacher;
 
+pub use country::Country;
+pub use organization::Organization;
+pub use organization_invitation::OrganizationInvitation;
 pub use teacher::Teacher;
-pub use session::Session;
+pub use session::{Session, SessionLifetimes};
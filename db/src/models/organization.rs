@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::schema::organizations;
+
+pub const ADMIN_ROLE: &str = "admin";
+pub const MEMBER_ROLE: &str = "member";
+pub const ROLES: [&str; 2] = [ADMIN_ROLE, MEMBER_ROLE];
+
+#[derive(PartialEq, Identifiable, Queryable, Debug)]
+pub struct Organization {
+  pub id: i32,
+  pub uuid: String,
+  pub name: String,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Insertable)]
+#[table_name = "organizations"]
+pub struct NewOrganization {
+  pub uuid: String,
+  pub name: String,
+}
+
+impl Default for NewOrganization {
+  fn default() -> Self {
+    Self {
+      uuid: Uuid::new_v4().to_string(),
+      name: String::new(),
+    }
+  }
+}
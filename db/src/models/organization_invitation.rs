@@ -0,0 +1,44 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::schema::organization_invitations;
+use crate::models::organization::MEMBER_ROLE;
+use crate::utils::token;
+
+#[derive(PartialEq, Identifiable, AsChangeset, Queryable, Debug)]
+pub struct OrganizationInvitation {
+  pub id: i32,
+  pub uuid: String,
+  pub organization_id: i32,
+  pub email: String,
+  pub role: String,
+  pub token: String,
+  pub expires_at: DateTime<Utc>,
+  pub accepted_at: Option<DateTime<Utc>>,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Insertable)]
+#[table_name = "organization_invitations"]
+pub struct NewOrganizationInvitation {
+  pub uuid: String,
+  pub organization_id: i32,
+  pub email: String,
+  pub role: String,
+  pub token: String,
+  pub expires_at: DateTime<Utc>,
+}
+
+impl Default for NewOrganizationInvitation {
+  fn default() -> Self {
+    Self {
+      uuid: Uuid::new_v4().to_string(),
+      organization_id: 0,
+      email: String::new(),
+      role: String::from(MEMBER_ROLE),
+      token: token::generate(),
+      expires_at: Utc::now() + Duration::weeks(1),
+    }
+  }
+}
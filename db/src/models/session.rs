@@ -30,17 +30,32 @@ pub struct NewTeacherSession {
   pub access_token_expires_at: DateTime<Utc>,
 }
 
-impl Default for NewTeacherSession {
+// How long the tokens of new and refreshed sessions stay valid
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SessionLifetimes {
+  pub access_token: Duration,
+  pub refresh_token: Duration,
+}
+
+impl Default for SessionLifetimes {
   fn default() -> Self {
+    Self {
+      access_token: Duration::days(1),
+      refresh_token: Duration::weeks(4),
+    }
+  }
+}
+
+impl NewTeacherSession {
+  pub fn new(owner_uuid: String, lifetimes: &SessionLifetimes) -> Self {
     Self {
       uuid: Uuid::new_v4().to_string(),
       owner_type: String::from("teacher"),
-      owner_uuid: String::new(),
+      owner_uuid,
       refresh_token: token::generate(),
-      refresh_token_expires_at: Utc::now() + Duration::weeks(4),
+      refresh_token_expires_at: Utc::now() + lifetimes.refresh_token,
       access_token: token::generate(),
-      access_token_expires_at: Utc::now() + Duration::days(1),
+      access_token_expires_at: Utc::now() + lifetimes.access_token,
     }
   }
 }
-
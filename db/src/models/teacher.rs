@@ -11,6 +11,9 @@ pub struct Teacher {
   pub password_digest: String,
   pub created_at: DateTime<Utc>,
   pub updated_at: DateTime<Utc>,
+  pub organization_id: Option<i32>,
+  pub organization_role: Option<String>,
+  pub deactivated_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Insertable)]
@@ -0,0 +1,195 @@
+use diesel::prelude::*;
+use diesel::pg::upsert::excluded;
+use diesel::result::Error;
+
+use crate::utils::errors::DbError;
+use crate::utils::types::DbConnection;
+use crate::models::country::{Country, NewCountry};
+use crate::repositories::Repository;
+use crate::schema;
+
+pub struct CountriesRepository<'a> {
+  db: &'a DbConnection,
+}
+
+impl<'a> Repository<'a> for CountriesRepository<'a> {
+  type Db = DbConnection;
+
+  fn new(db: &'a DbConnection) -> Self {
+    Self { db }
+  }
+}
+
+impl<'a> CountriesRepository<'a> {
+  pub fn count(&self) -> Result<i64, DbError> {
+    use diesel::dsl::count;
+    use schema::countries::dsl::*;
+
+    countries.select(count(id))
+      .first(self.db)
+      .map_err(|error| error.into())
+  }
+
+  pub fn all(&self) -> Result<Vec<Country>, DbError> {
+    use schema::countries::dsl::*;
+
+    countries.order(alpha2_code)
+      .load::<Country>(self.db)
+      .map_err(|error| error.into())
+  }
+
+  pub fn by_continent(&self, country_continent: &str) -> Result<Vec<Country>, DbError> {
+    use schema::countries::dsl::*;
+
+    countries.filter(continent.eq(country_continent))
+      .order(alpha2_code)
+      .load::<Country>(self.db)
+      .map_err(|error| error.into())
+  }
+
+  pub fn find_by_alpha2_code(&self, country_alpha2_code: &str) -> Result<Country, DbError> {
+    use schema::countries::dsl::*;
+
+    countries.filter(alpha2_code.eq(country_alpha2_code))
+      .first::<Country>(self.db)
+      .map_err(|err| match err {
+        Error::NotFound => DbError::RecordNotFound,
+        error => error.into(),
+      })
+  }
+
+  // Inserts the given countries, overwriting the existing ones with the same alpha-2 code,
+  // so that re-importing the reference dataset picks up corrections instead of failing.
+  pub fn upsert(&self, new_countries: &[NewCountry]) -> Result<usize, DbError> {
+    use schema::countries::dsl::*;
+
+    diesel::insert_into(countries)
+      .values(new_countries)
+      .on_conflict(alpha2_code)
+      .do_update()
+      .set((
+        alpha3_code.eq(excluded(alpha3_code)),
+        name_pl.eq(excluded(name_pl)),
+        name_en.eq(excluded(name_en)),
+        capital_pl.eq(excluded(capital_pl)),
+        capital_en.eq(excluded(capital_en)),
+        continent.eq(excluded(continent)),
+        neighbours.eq(excluded(neighbours)),
+      ))
+      .execute(self.db)
+      .map_err(|error| error.into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::utils::test::with_db;
+  use super::*;
+
+  fn poland() -> NewCountry {
+    NewCountry {
+      alpha2_code: "PL".into(),
+      alpha3_code: "POL".into(),
+      name_pl: "Polska".into(),
+      name_en: "Poland".into(),
+      capital_pl: "Warszawa".into(),
+      capital_en: "Warsaw".into(),
+      continent: "europe".into(),
+      neighbours: vec!["CZ".into(), "DE".into(), "SK".into()],
+    }
+  }
+
+  fn japan() -> NewCountry {
+    NewCountry {
+      alpha2_code: "JP".into(),
+      alpha3_code: "JPN".into(),
+      name_pl: "Japonia".into(),
+      name_en: "Japan".into(),
+      capital_pl: "Tokio".into(),
+      capital_en: "Tokyo".into(),
+      continent: "asia".into(),
+      neighbours: vec![],
+    }
+  }
+
+  #[test]
+  fn count_works() {
+    with_db(|connection| {
+      let count = CountriesRepository::new(&connection).count();
+      assert!(count.is_ok());
+      assert_eq!(count.unwrap(), 0);
+    })
+  }
+
+  #[test]
+  fn upsert_works() {
+    with_db(|connection| {
+      let repository = CountriesRepository::new(&connection);
+
+      assert_eq!(repository.upsert(&[poland(), japan()]), Ok(2));
+      assert_eq!(repository.count().unwrap(), 2);
+    })
+  }
+
+  #[test]
+  fn upsert_updates_existing_countries() {
+    with_db(|connection| {
+      let repository = CountriesRepository::new(&connection);
+      repository.upsert(&[poland()]).unwrap();
+      let updated_poland = NewCountry { capital_en: "Warszawa".into(), ..poland() };
+
+      assert!(repository.upsert(&[updated_poland]).is_ok());
+      assert_eq!(repository.count().unwrap(), 1);
+      assert_eq!(repository.find_by_alpha2_code("PL").unwrap().capital_en, "Warszawa");
+    })
+  }
+
+  #[test]
+  fn all_works() {
+    with_db(|connection| {
+      let repository = CountriesRepository::new(&connection);
+      repository.upsert(&[poland(), japan()]).unwrap();
+
+      let codes: Vec<String> = repository.all().unwrap()
+        .into_iter()
+        .map(|country| country.alpha2_code)
+        .collect();
+      assert_eq!(codes, vec!["JP", "PL"]);
+    })
+  }
+
+  #[test]
+  fn by_continent_works() {
+    with_db(|connection| {
+      let repository = CountriesRepository::new(&connection);
+      repository.upsert(&[poland(), japan()]).unwrap();
+
+      let countries = repository.by_continent("europe").unwrap();
+      assert_eq!(countries.len(), 1);
+      assert_eq!(countries[0].alpha2_code, "PL");
+      assert!(repository.by_continent("oceania").unwrap().is_empty());
+    })
+  }
+
+  #[test]
+  fn find_by_alpha2_code_works() {
+    with_db(|connection| {
+      let repository = CountriesRepository::new(&connection);
+      repository.upsert(&[poland()]).unwrap();
+
+      let found_country = repository.find_by_alpha2_code("PL");
+      assert!(found_country.is_ok());
+      assert_eq!(found_country.unwrap().neighbours, vec!["CZ", "DE", "SK"]);
+    })
+  }
+
+  #[test]
+  fn find_by_alpha2_code_fails_when_country_doesnt_exist() {
+    with_db(|connection| {
+      assert_eq!(
+        CountriesRepository::new(&connection).find_by_alpha2_code("PL"),
+        Err(DbError::RecordNotFound),
+      );
+    })
+  }
+}
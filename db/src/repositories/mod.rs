@@ -1,7 +1,13 @@
 mod repository;
+mod countries_repository;
+mod organizations_repository;
+mod organization_invitations_repository;
 mod teachers_repository;
 mod sessions_repository;
 
+pub use countries_repository::CountriesRepository;
+pub use organizations_repository::OrganizationsRepository;
+pub use organization_invitations_repository::OrganizationInvitationsRepository;
 pub use teachers_repository::TeachersRepository;
 pub use sessions_repository::SessionsRepository;
 pub use repository::Repository;
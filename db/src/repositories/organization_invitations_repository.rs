@@ -0,0 +1,222 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::result::Error;
+
+use crate::utils::errors::DbError;
+use crate::utils::types::DbConnection;
+use crate::models::Organization;
+use crate::models::organization_invitation::{OrganizationInvitation, NewOrganizationInvitation};
+use crate::repositories::Repository;
+use crate::schema;
+
+pub struct OrganizationInvitationsRepository<'a> {
+  db: &'a DbConnection,
+}
+
+impl<'a> Repository<'a> for OrganizationInvitationsRepository<'a> {
+  type Db = DbConnection;
+
+  fn new(db: &'a DbConnection) -> Self {
+    Self { db }
+  }
+}
+
+impl<'a> OrganizationInvitationsRepository<'a> {
+  pub fn count(&self) -> Result<i64, DbError> {
+    use diesel::dsl::count;
+    use schema::organization_invitations::dsl::*;
+
+    organization_invitations.select(count(id))
+      .first(self.db)
+      .map_err(|error| error.into())
+  }
+
+  pub fn find_by_token(&self, invitation_token: &str) -> Result<OrganizationInvitation, DbError> {
+    use schema::organization_invitations::dsl::*;
+
+    organization_invitations.filter(token.eq(invitation_token))
+      .first::<OrganizationInvitation>(self.db)
+      .map_err(|err| match err {
+        Error::NotFound => DbError::RecordNotFound,
+        error => error.into(),
+      })
+  }
+
+  pub fn create(
+    &self,
+    organization: &Organization,
+    email: String,
+    role: String,
+  ) -> Result<OrganizationInvitation, DbError> {
+    let new_invitation = NewOrganizationInvitation {
+      organization_id: organization.id,
+      email,
+      role,
+      ..Default::default()
+    };
+
+    diesel::insert_into(schema::organization_invitations::table)
+      .values(&new_invitation)
+      .get_result::<OrganizationInvitation>(self.db)
+      .map_err(|error| error.into())
+  }
+
+  // Only an invitation that hasn't been accepted yet is updated, so that when the same invitation
+  // is accepted concurrently, only one of the acceptances succeeds
+  pub fn accept(&self, invitation: &OrganizationInvitation) -> Result<OrganizationInvitation, DbError> {
+    use schema::organization_invitations::dsl::*;
+
+    let pending_invitation = organization_invitations
+      .filter(id.eq(invitation.id))
+      .filter(accepted_at.is_null());
+
+    diesel::update(pending_invitation)
+      .set(accepted_at.eq(Utc::now()))
+      .get_result::<OrganizationInvitation>(self.db)
+      .map_err(|err| match err {
+        Error::NotFound => DbError::RecordNotFound,
+        error => error.into(),
+      })
+  }
+
+  pub fn save(&self, invitation: &OrganizationInvitation) -> Result<OrganizationInvitation, DbError> {
+    diesel::update(invitation)
+      .set(invitation)
+      .get_result::<OrganizationInvitation>(self.db)
+      .map_err(|err| match err {
+        Error::NotFound => {
+          DbError::NotFound("organization_invitation", "id", invitation.id.to_string())
+        },
+        error => error.into(),
+      })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::repositories::OrganizationsRepository;
+  use crate::utils::test::factories::unique_email;
+  use crate::utils::test::with_db;
+  use super::*;
+
+  #[test]
+  fn count_works() {
+    with_db(|connection| {
+      let count = OrganizationInvitationsRepository::new(&connection).count();
+      assert!(count.is_ok());
+      assert_eq!(count.unwrap(), 0);
+    })
+  }
+
+  #[test]
+  fn create_works() {
+    with_db(|connection| {
+      let organizations_repository = OrganizationsRepository::new(&connection);
+      let invitations_repository = OrganizationInvitationsRepository::new(&connection);
+      let organization = organizations_repository.create("SP 1 Warszawa".into()).unwrap();
+
+      let invitation = invitations_repository
+        .create(&organization, unique_email(), "member".into());
+      assert!(invitation.is_ok());
+      assert_eq!(invitation.unwrap().accepted_at, None);
+      assert_eq!(invitations_repository.count().unwrap(), 1);
+    })
+  }
+
+  #[test]
+  fn find_by_token_works() {
+    with_db(|connection| {
+      let organizations_repository = OrganizationsRepository::new(&connection);
+      let invitations_repository = OrganizationInvitationsRepository::new(&connection);
+      let organization = organizations_repository.create("SP 1 Warszawa".into()).unwrap();
+      let invitation = invitations_repository
+        .create(&organization, unique_email(), "member".into())
+        .unwrap();
+
+      let found_invitation = invitations_repository.find_by_token(&invitation.token);
+      assert!(found_invitation.is_ok());
+      assert_eq!(found_invitation.unwrap().id, invitation.id);
+    })
+  }
+
+  #[test]
+  fn find_by_token_fails_when_invitation_doesnt_exist() {
+    with_db(|connection| {
+      assert_eq!(
+        OrganizationInvitationsRepository::new(&connection).find_by_token("some-token"),
+        Err(DbError::RecordNotFound),
+      );
+    })
+  }
+
+  #[test]
+  fn accept_works() {
+    with_db(|connection| {
+      let organizations_repository = OrganizationsRepository::new(&connection);
+      let invitations_repository = OrganizationInvitationsRepository::new(&connection);
+      let organization = organizations_repository.create("SP 1 Warszawa".into()).unwrap();
+      let invitation = invitations_repository
+        .create(&organization, unique_email(), "member".into())
+        .unwrap();
+
+      let result = invitations_repository.accept(&invitation);
+      assert!(result.is_ok());
+      assert!(result.unwrap().accepted_at.is_some());
+    })
+  }
+
+  #[test]
+  fn accept_fails_when_invitation_is_already_accepted() {
+    with_db(|connection| {
+      let organizations_repository = OrganizationsRepository::new(&connection);
+      let invitations_repository = OrganizationInvitationsRepository::new(&connection);
+      let organization = organizations_repository.create("SP 1 Warszawa".into()).unwrap();
+      let invitation = invitations_repository
+        .create(&organization, unique_email(), "member".into())
+        .unwrap();
+      let accepted_invitation = invitations_repository.accept(&invitation).unwrap();
+
+      // The invitation was loaded before the other acceptance, so it still looks pending
+      assert_eq!(invitations_repository.accept(&invitation), Err(DbError::RecordNotFound));
+      assert_eq!(
+        invitations_repository.find_by_token(&invitation.token).unwrap().accepted_at,
+        accepted_invitation.accepted_at,
+      );
+    })
+  }
+
+  #[test]
+  fn save_works() {
+    with_db(|connection| {
+      let organizations_repository = OrganizationsRepository::new(&connection);
+      let invitations_repository = OrganizationInvitationsRepository::new(&connection);
+      let organization = organizations_repository.create("SP 1 Warszawa".into()).unwrap();
+      let mut invitation = invitations_repository
+        .create(&organization, unique_email(), "member".into())
+        .unwrap();
+      invitation.role = "admin".into();
+
+      let result = invitations_repository.save(&invitation);
+      assert!(result.is_ok());
+      assert_eq!(result.unwrap().role, "admin");
+    })
+  }
+
+  #[test]
+  fn save_fails_when_invitation_doesnt_exist() {
+    with_db(|connection| {
+      let organizations_repository = OrganizationsRepository::new(&connection);
+      let invitations_repository = OrganizationInvitationsRepository::new(&connection);
+      let organization = organizations_repository.create("SP 1 Warszawa".into()).unwrap();
+      let mut invitation = invitations_repository
+        .create(&organization, unique_email(), "member".into())
+        .unwrap();
+      invitation.id += 2137;
+
+      assert_eq!(
+        invitations_repository.save(&invitation),
+        Err(DbError::NotFound("organization_invitation", "id", invitation.id.to_string())),
+      );
+    })
+  }
+}
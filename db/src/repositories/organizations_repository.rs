@@ -0,0 +1,130 @@
+use diesel::prelude::*;
+use diesel::result::Error;
+
+use crate::utils::errors::DbError;
+use crate::utils::types::DbConnection;
+use crate::models::organization::{Organization, NewOrganization};
+use crate::repositories::Repository;
+use crate::schema;
+
+pub struct OrganizationsRepository<'a> {
+  db: &'a DbConnection,
+}
+
+impl<'a> Repository<'a> for OrganizationsRepository<'a> {
+  type Db = DbConnection;
+
+  fn new(db: &'a DbConnection) -> Self {
+    Self { db }
+  }
+}
+
+impl<'a> OrganizationsRepository<'a> {
+  pub fn count(&self) -> Result<i64, DbError> {
+    use diesel::dsl::count;
+    use schema::organizations::dsl::*;
+
+    organizations.select(count(id))
+      .first(self.db)
+      .map_err(|error| error.into())
+  }
+
+  pub fn find_by_uuid(&self, organization_uuid: &str) -> Result<Organization, DbError> {
+    use schema::organizations::dsl::*;
+
+    organizations.filter(uuid.eq(organization_uuid))
+      .first::<Organization>(self.db)
+      .map_err(|err| match err {
+        Error::NotFound => DbError::RecordNotFound,
+        error => error.into(),
+      })
+  }
+
+  pub fn find_by_id(&self, organization_id: i32) -> Result<Organization, DbError> {
+    use schema::organizations::dsl::*;
+
+    organizations.find(organization_id)
+      .first::<Organization>(self.db)
+      .map_err(|err| match err {
+        Error::NotFound => DbError::RecordNotFound,
+        error => error.into(),
+      })
+  }
+
+  pub fn create(&self, name: String) -> Result<Organization, DbError> {
+    let new_organization = NewOrganization { name, ..Default::default() };
+
+    diesel::insert_into(schema::organizations::table)
+      .values(&new_organization)
+      .get_result::<Organization>(self.db)
+      .map_err(|error| error.into())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::utils::test::with_db;
+  use super::*;
+
+  #[test]
+  fn count_works() {
+    with_db(|connection| {
+      let count = OrganizationsRepository::new(&connection).count();
+      assert!(count.is_ok());
+      assert_eq!(count.unwrap(), 0);
+    })
+  }
+
+  #[test]
+  fn create_works() {
+    with_db(|connection| {
+      let repository = OrganizationsRepository::new(&connection);
+      assert!(repository.create("SP 1 Warszawa".into()).is_ok());
+      assert_eq!(repository.count().unwrap(), 1);
+    })
+  }
+
+  #[test]
+  fn find_by_id_works() {
+    with_db(|connection| {
+      let repository = OrganizationsRepository::new(&connection);
+      let organization = repository.create("SP 1 Warszawa".into()).unwrap();
+
+      let found_organization = repository.find_by_id(organization.id);
+      assert!(found_organization.is_ok());
+      assert_eq!(found_organization.unwrap().uuid, organization.uuid);
+    })
+  }
+
+  #[test]
+  fn find_by_id_fails_when_organization_doesnt_exist() {
+    with_db(|connection| {
+      assert_eq!(
+        OrganizationsRepository::new(&connection).find_by_id(2137),
+        Err(DbError::RecordNotFound),
+      );
+    })
+  }
+
+  #[test]
+  fn find_by_uuid_works() {
+    with_db(|connection| {
+      let repository = OrganizationsRepository::new(&connection);
+      let organization = repository.create("SP 1 Warszawa".into()).unwrap();
+
+      let found_organization = repository.find_by_uuid(&organization.uuid);
+      assert!(found_organization.is_ok());
+      assert_eq!(found_organization.unwrap().id, organization.id);
+    })
+  }
+
+  #[test]
+  fn find_by_uuid_fails_when_organization_doesnt_exist() {
+    with_db(|connection| {
+      assert_eq!(
+        OrganizationsRepository::new(&connection).find_by_uuid("some-uuid"),
+        Err(DbError::RecordNotFound),
+      );
+    })
+  }
+}
@@ -1,6 +1,7 @@
-use crate::utils::types::DbConnection;
-
+// Implemented by the synchronous repositories as well as by the async ones, which run their
+// queries on the connection they're given, be it a transaction of a unit of work or not
 pub trait Repository<'a> {
-  fn new(db: &'a DbConnection) -> Self;
-}
+  type Db: ?Sized + 'a;
 
+  fn new(db: &'a Self::Db) -> Self;
+}
@@ -4,7 +4,7 @@ use diesel::result::Error;
 use crate::utils::errors::DbError;
 use crate::utils::types::DbConnection;
 use crate::models::Teacher;
-use crate::models::session::{Session, NewTeacherSession};
+use crate::models::session::{Session, SessionLifetimes, NewTeacherSession};
 use crate::repositories::Repository;
 use crate::schema;
 
@@ -13,6 +13,8 @@ pub struct SessionsRepository<'a> {
 }
 
 impl<'a> Repository<'a> for SessionsRepository<'a> {
+  type Db = DbConnection;
+
   fn new(db: &'a DbConnection) -> Self {
     Self { db }
   }
@@ -39,8 +41,23 @@ impl<'a> SessionsRepository<'a> {
       })
   }
 
-  pub fn create(&self, teacher: &Teacher) -> Result<Session, DbError> {
-    let new_session = NewTeacherSession { owner_uuid: teacher.uuid.clone(), ..Default::default() };
+  pub fn find_by_access_token(&self, session_access_token: &str) -> Result<Session, DbError> {
+    use schema::sessions::dsl::*;
+
+    sessions.filter(access_token.eq(session_access_token))
+      .first::<Session>(self.db)
+      .map_err(|err| match err {
+        Error::NotFound => DbError::RecordNotFound,
+        error => error.into(),
+      })
+  }
+
+  pub fn create(
+    &self,
+    teacher: &Teacher,
+    lifetimes: &SessionLifetimes,
+  ) -> Result<Session, DbError> {
+    let new_session = NewTeacherSession::new(teacher.uuid.clone(), lifetimes);
 
     diesel::insert_into(schema::sessions::table)
       .values(&new_session)
@@ -69,17 +86,23 @@ impl<'a> SessionsRepository<'a> {
       Err(error) => Err(DbError::UnexpectedError(error)),
     }
   }
+
+  pub fn destroy_all_by_owner(&self, teacher: &Teacher) -> Result<usize, DbError> {
+    use schema::sessions::dsl::*;
+
+    diesel::delete(sessions.filter(owner_type.eq("teacher")).filter(owner_uuid.eq(&teacher.uuid)))
+      .execute(self.db)
+      .map_err(|error| error.into())
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use serial_test::serial;
-  use crate::repositories::TeachersRepository;
+  use crate::utils::test::factories::TeacherFactory;
   use crate::utils::test::with_db;
   use super::*;
 
   #[test]
-  #[serial]
   fn count_works() {
     with_db(|connection| {
       let count = SessionsRepository::new(&connection).count();
@@ -89,28 +112,22 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn create_works() {
     with_db(|connection| {
-      let teachers_repository = TeachersRepository::new(&connection);
       let sessions_repository = SessionsRepository::new(&connection);
-      let teacher = teachers_repository.create("john.doe@example.com".into(), "test".into()).unwrap();
+      let teacher = TeacherFactory::new(&connection).create();
 
-      assert!(sessions_repository.create(&teacher).is_ok());
+      assert!(sessions_repository.create(&teacher, &SessionLifetimes::default()).is_ok());
       assert_eq!(sessions_repository.count().unwrap(), 1);
     })
   }
 
   #[test]
-  #[serial]
   fn find_by_uuid_works() {
     with_db(|connection| {
-      let teachers_repository = TeachersRepository::new(&connection);
       let sessions_repository = SessionsRepository::new(&connection);
-      let teacher = teachers_repository
-        .create("john.doe@example.com".into(), "test".into())
-        .unwrap();
-      let session = sessions_repository.create(&teacher).unwrap();
+      let teacher = TeacherFactory::new(&connection).create();
+      let session = sessions_repository.create(&teacher, &SessionLifetimes::default()).unwrap();
 
       let found_session = sessions_repository.find_by_uuid(&session.uuid);
       assert!(found_session.is_ok());
@@ -129,13 +146,34 @@ mod tests {
   }
 
   #[test]
-  #[serial]
+  fn find_by_access_token_works() {
+    with_db(|connection| {
+      let sessions_repository = SessionsRepository::new(&connection);
+      let teacher = TeacherFactory::new(&connection).create();
+      let session = sessions_repository.create(&teacher, &SessionLifetimes::default()).unwrap();
+
+      let found_session = sessions_repository.find_by_access_token(&session.access_token);
+      assert!(found_session.is_ok());
+      assert_eq!(found_session.unwrap().id, session.id);
+    })
+  }
+
+  #[test]
+  fn find_by_access_token_fails_when_session_doesnt_exist() {
+    with_db(|connection| {
+      assert_eq!(
+        SessionsRepository::new(&connection).find_by_access_token("some_token"),
+        Err(DbError::RecordNotFound)
+      );
+    })
+  }
+
+  #[test]
   fn save_works() {
     with_db(|connection| {
-      let teachers_repository = TeachersRepository::new(&connection);
       let sessions_repository = SessionsRepository::new(&connection);
-      let teacher = teachers_repository.create("john.doe@example.com".into(), "test".into()).unwrap();
-      let mut session = sessions_repository.create(&teacher).unwrap();
+      let teacher = TeacherFactory::new(&connection).create();
+      let mut session = sessions_repository.create(&teacher, &SessionLifetimes::default()).unwrap();
       let new_uuid = "new-uuid".to_string();
       session.owner_uuid = new_uuid.clone();
 
@@ -146,14 +184,12 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn save_fails_when_session_doesnt_exist() {
     with_db(|connection| {
-      let teachers_repository = TeachersRepository::new(&connection);
       let sessions_repository = SessionsRepository::new(&connection);
-      let teacher = teachers_repository.create("john.doe@example.com".into(), "test".into()).unwrap();
-      let mut session = sessions_repository.create(&teacher).unwrap();
-      session.id = session.id + 2137;
+      let teacher = TeacherFactory::new(&connection).create();
+      let mut session = sessions_repository.create(&teacher, &SessionLifetimes::default()).unwrap();
+      session.id += 2137;
 
       assert_eq!(
         sessions_repository.save(&session),
@@ -163,13 +199,11 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn destroy_works() {
     with_db(|connection| {
-      let teachers_repository = TeachersRepository::new(&connection);
       let sessions_repository = SessionsRepository::new(&connection);
-      let teacher = teachers_repository.create("john.doe@example.com".into(), "test".into()).unwrap();
-      let session = sessions_repository.create(&teacher).unwrap();
+      let teacher = TeacherFactory::new(&connection).create();
+      let session = sessions_repository.create(&teacher, &SessionLifetimes::default()).unwrap();
 
       assert!(sessions_repository.destroy(&session).is_ok());
       assert_eq!(sessions_repository.count().unwrap(), 0);
@@ -177,14 +211,12 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn destroy_fails_when_session_doesnt_exists() {
     with_db(|connection| {
-      let teachers_repository = TeachersRepository::new(&connection);
       let sessions_repository = SessionsRepository::new(&connection);
-      let teacher = teachers_repository.create("john.doe@example.com".into(), "test".into()).unwrap();
-      let mut session = sessions_repository.create(&teacher).unwrap();
-      session.id = session.id + 2137;
+      let teacher = TeacherFactory::new(&connection).create();
+      let mut session = sessions_repository.create(&teacher, &SessionLifetimes::default()).unwrap();
+      session.id += 2137;
 
       assert_eq!(
         sessions_repository.destroy(&session),
@@ -193,4 +225,19 @@ mod tests {
       assert_eq!(sessions_repository.count().unwrap(), 1);
     })
   }
+
+  #[test]
+  fn destroy_all_by_owner_works() {
+    with_db(|connection| {
+      let sessions_repository = SessionsRepository::new(&connection);
+      let teacher = TeacherFactory::new(&connection).create();
+      let other_teacher = TeacherFactory::new(&connection).create();
+      sessions_repository.create(&teacher, &SessionLifetimes::default()).unwrap();
+      sessions_repository.create(&teacher, &SessionLifetimes::default()).unwrap();
+      sessions_repository.create(&other_teacher, &SessionLifetimes::default()).unwrap();
+
+      assert_eq!(sessions_repository.destroy_all_by_owner(&teacher), Ok(2));
+      assert_eq!(sessions_repository.count().unwrap(), 1);
+    })
+  }
 }
@@ -1,8 +1,10 @@
+use chrono::Utc;
 use diesel::prelude::*;
 use diesel::result::Error;
 
 use crate::utils::errors::DbError;
 use crate::utils::types::DbConnection;
+use crate::models::Organization;
 use crate::models::teacher::{Teacher, NewTeacher};
 use crate::repositories::Repository;
 use crate::schema;
@@ -12,6 +14,8 @@ pub struct TeachersRepository<'a> {
 }
 
 impl<'a> Repository<'a> for TeachersRepository<'a> {
+  type Db = DbConnection;
+
   fn new(db: &'a DbConnection) -> Self {
     Self { db }
   }
@@ -57,16 +61,54 @@ impl<'a> TeachersRepository<'a> {
       .get_result::<Teacher>(self.db)
       .map_err(|error| error.into())
   }
+
+  pub fn by_organization(&self, organization: &Organization) -> Result<Vec<Teacher>, DbError> {
+    use schema::teachers::dsl::*;
+
+    teachers.filter(organization_id.eq(organization.id))
+      .order(email)
+      .load::<Teacher>(self.db)
+      .map_err(|error| error.into())
+  }
+
+  pub fn join_organization(
+    &self,
+    teacher: &Teacher,
+    organization: &Organization,
+    role: &str,
+  ) -> Result<Teacher, DbError> {
+    use schema::teachers::dsl::*;
+
+    diesel::update(teacher)
+      .set((organization_id.eq(organization.id), organization_role.eq(role)))
+      .get_result::<Teacher>(self.db)
+      .map_err(|err| match err {
+        Error::NotFound => DbError::NotFound("teacher", "id", teacher.id.to_string()),
+        error => error.into(),
+      })
+  }
+
+  pub fn deactivate(&self, teacher: &Teacher) -> Result<Teacher, DbError> {
+    use schema::teachers::dsl::*;
+
+    diesel::update(teacher)
+      .set(deactivated_at.eq(Utc::now()))
+      .get_result::<Teacher>(self.db)
+      .map_err(|err| match err {
+        Error::NotFound => DbError::NotFound("teacher", "id", teacher.id.to_string()),
+        error => error.into(),
+      })
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use serial_test::serial;
+  use crate::repositories::OrganizationsRepository;
+  use crate::utils::test::factories::{unique_email, TeacherFactory};
   use crate::utils::test::with_db;
   use super::*;
 
   #[test]
-  #[serial]
   fn count_works() {
     with_db(|connection| {
       let count = TeachersRepository::new(&connection).count();
@@ -76,40 +118,36 @@ mod tests {
   }
 
   #[test]
-  #[serial]
   fn create_works() {
     with_db(|connection| {
       let repository = TeachersRepository::new(&connection);
-      assert!(repository.create("test".into(), "test".into()).is_ok());
+      assert!(repository.create(unique_email(), "test".into()).is_ok());
       assert_eq!(repository.count().unwrap(), 1);
     })
   }
 
   #[test]
-  #[serial]
   fn create_fails_when_email_is_taken() {
     with_db(|connection| {
       let repository = TeachersRepository::new(&connection);
-      let email = "john.doe@example.com";
-      repository.create(email.into(), "test1".into()).unwrap();
+      let email = unique_email();
+      repository.create(email.clone(), "test1".into()).unwrap();
 
-      match repository.create(email.into(), "test2".into()) {
-        Err(DbError::UniqueConstraintViolation(_)) => (),
-        _ => assert!(false),
-      }
+      // The failed insert would abort the test transaction without the savepoint
+      let result = connection.transaction(|| repository.create(email.clone(), "test2".into()));
+      assert!(matches!(result, Err(DbError::UniqueConstraintViolation(_))));
       assert_eq!(repository.count().unwrap(), 1);
     })
   }
 
   #[test]
-  #[serial]
   fn find_by_email_works() {
     with_db(|connection| {
       let repository = TeachersRepository::new(&connection);
-      let email = "john.doe@example.com";
-      let teacher = repository.create(email.into(), "test1".into()).unwrap();
+      let email = unique_email();
+      let teacher = repository.create(email.clone(), "test1".into()).unwrap();
 
-      let found_teacher = repository.find_by_email(email);
+      let found_teacher = repository.find_by_email(&email);
       assert!(found_teacher.is_ok());
       assert_eq!(found_teacher.unwrap().id, teacher.id);
     })
@@ -118,21 +156,18 @@ mod tests {
   #[test]
   fn find_by_email_fails_when_teacher_doesnt_exist() {
     with_db(|connection| {
-      let email = "john.doe@example.com";
-
       assert_eq!(
-        TeachersRepository::new(&connection).find_by_email(email),
+        TeachersRepository::new(&connection).find_by_email(&unique_email()),
         Err(DbError::RecordNotFound),
       );
     })
   }
 
   #[test]
-  #[serial]
   fn find_by_uuid_works() {
     with_db(|connection| {
       let repository = TeachersRepository::new(&connection);
-      let teacher = repository.create("john.doe@example.com".into(), "test1".into()).unwrap();
+      let teacher = TeacherFactory::new(&connection).create();
 
       let found_teacher = repository.find_by_uuid(&teacher.uuid);
       assert!(found_teacher.is_ok());
@@ -151,4 +186,62 @@ mod tests {
       );
     })
   }
+
+  #[test]
+  fn by_organization_works() {
+    with_db(|connection| {
+      let organizations_repository = OrganizationsRepository::new(&connection);
+      let teachers_repository = TeachersRepository::new(&connection);
+      let organization = organizations_repository.create("SP 1 Warszawa".into()).unwrap();
+      let teacher = TeacherFactory::new(&connection).create();
+      TeacherFactory::new(&connection).create();
+      teachers_repository.join_organization(&teacher, &organization, "member").unwrap();
+
+      let members = teachers_repository.by_organization(&organization);
+      assert!(members.is_ok());
+      assert_eq!(members.unwrap().iter().map(|t| t.id).collect::<Vec<i32>>(), vec![teacher.id]);
+    })
+  }
+
+  #[test]
+  fn join_organization_works() {
+    with_db(|connection| {
+      let organizations_repository = OrganizationsRepository::new(&connection);
+      let teachers_repository = TeachersRepository::new(&connection);
+      let organization = organizations_repository.create("SP 1 Warszawa".into()).unwrap();
+      let teacher = TeacherFactory::new(&connection).create();
+
+      let result = teachers_repository.join_organization(&teacher, &organization, "admin");
+      assert!(result.is_ok());
+      let teacher = result.unwrap();
+      assert_eq!(teacher.organization_id, Some(organization.id));
+      assert_eq!(teacher.organization_role, Some("admin".into()));
+    })
+  }
+
+  #[test]
+  fn deactivate_works() {
+    with_db(|connection| {
+      let repository = TeachersRepository::new(&connection);
+      let teacher = TeacherFactory::new(&connection).create();
+
+      let result = repository.deactivate(&teacher);
+      assert!(result.is_ok());
+      assert!(result.unwrap().deactivated_at.is_some());
+    })
+  }
+
+  #[test]
+  fn deactivate_fails_when_teacher_doesnt_exist() {
+    with_db(|connection| {
+      let repository = TeachersRepository::new(&connection);
+      let mut teacher = TeacherFactory::new(&connection).create();
+      teacher.id += 2137;
+
+      assert_eq!(
+        repository.deactivate(&teacher),
+        Err(DbError::NotFound("teacher", "id", teacher.id.to_string())),
+      );
+    })
+  }
 }
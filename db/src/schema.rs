@@ -1,3 +1,44 @@
+table! {
+    countries (id) {
+        id -> Int4,
+        alpha2_code -> Varchar,
+        alpha3_code -> Varchar,
+        name_pl -> Varchar,
+        name_en -> Varchar,
+        capital_pl -> Varchar,
+        capital_en -> Varchar,
+        continent -> Varchar,
+        neighbours -> Array<Varchar>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+table! {
+    organization_invitations (id) {
+        id -> Int4,
+        uuid -> Varchar,
+        organization_id -> Int4,
+        email -> Varchar,
+        role -> Varchar,
+        token -> Varchar,
+        expires_at -> Timestamptz,
+        accepted_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+table! {
+    organizations (id) {
+        id -> Int4,
+        uuid -> Varchar,
+        name -> Varchar,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 table! {
     sessions (id) {
         id -> Int4,
@@ -21,10 +62,19 @@ table! {
         password_digest -> Varchar,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        organization_id -> Nullable<Int4>,
+        organization_role -> Nullable<Varchar>,
+        deactivated_at -> Nullable<Timestamptz>,
     }
 }
 
+joinable!(organization_invitations -> organizations (organization_id));
+joinable!(teachers -> organizations (organization_id));
+
 allow_tables_to_appear_in_same_query!(
+    countries,
+    organization_invitations,
+    organizations,
     sessions,
     teachers,
 );
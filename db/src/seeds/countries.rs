@@ -0,0 +1,127 @@
+use serde::Deserialize;
+
+use crate::models::country::NewCountry;
+use crate::repositories::{CountriesRepository, Repository};
+use crate::utils::types::DbConnection;
+
+const COUNTRIES_CSV: &str = include_str!("../../data/countries.csv");
+
+#[derive(Deserialize)]
+struct Record {
+  alpha2_code: String,
+  alpha3_code: String,
+  name_pl: String,
+  name_en: String,
+  capital_pl: String,
+  capital_en: String,
+  continent: String,
+  // Space separated alpha-2 codes of the countries sharing a land border
+  neighbours: String,
+}
+
+impl From<Record> for NewCountry {
+  fn from(record: Record) -> Self {
+    NewCountry {
+      alpha2_code: record.alpha2_code,
+      alpha3_code: record.alpha3_code,
+      name_pl: record.name_pl,
+      name_en: record.name_en,
+      capital_pl: record.capital_pl,
+      capital_en: record.capital_en,
+      continent: record.continent,
+      neighbours: record.neighbours.split_whitespace().map(String::from).collect(),
+    }
+  }
+}
+
+pub fn load() -> Result<Vec<NewCountry>, String> {
+  csv::Reader::from_reader(COUNTRIES_CSV.as_bytes())
+    .deserialize::<Record>()
+    .map(|record| record.map(NewCountry::from))
+    .collect::<Result<Vec<NewCountry>, csv::Error>>()
+    .map_err(|err| format!("Failed to parse the countries dataset: {}", err))
+}
+
+pub fn import(db: &DbConnection) -> Result<usize, String> {
+  let countries = load()?;
+
+  CountriesRepository::new(db)
+    .upsert(&countries)
+    .map_err(|err| format!("Failed to import countries: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+  use crate::models::country::CONTINENTS;
+  use crate::utils::test::TestSchema;
+  use super::*;
+
+  #[test]
+  fn load_works() {
+    let countries = load();
+    assert!(countries.is_ok());
+    assert_eq!(countries.unwrap().len(), 196);
+  }
+
+  #[test]
+  fn dataset_has_unique_codes() {
+    let countries = load().unwrap();
+    let mut alpha2_codes: Vec<&str> = countries.iter().map(|c| c.alpha2_code.as_str()).collect();
+    let mut alpha3_codes: Vec<&str> = countries.iter().map(|c| c.alpha3_code.as_str()).collect();
+    alpha2_codes.sort();
+    alpha2_codes.dedup();
+    alpha3_codes.sort();
+    alpha3_codes.dedup();
+
+    assert_eq!(alpha2_codes.len(), countries.len());
+    assert_eq!(alpha3_codes.len(), countries.len());
+  }
+
+  #[test]
+  fn dataset_has_valid_continents() {
+    for country in load().unwrap() {
+      assert!(CONTINENTS.contains(&country.continent.as_str()), "{}", country.alpha2_code);
+    }
+  }
+
+  #[test]
+  fn dataset_has_symmetric_neighbours() {
+    let countries = load().unwrap();
+    let neighbours: HashMap<&str, &Vec<String>> = countries.iter()
+      .map(|c| (c.alpha2_code.as_str(), &c.neighbours))
+      .collect();
+
+    for country in &countries {
+      for neighbour in &country.neighbours {
+        match neighbours.get(neighbour.as_str()) {
+          Some(codes) => assert!(codes.contains(&country.alpha2_code), "{} - {}", country.alpha2_code, neighbour),
+          None => panic!("{} has an unknown neighbour {}", country.alpha2_code, neighbour),
+        }
+      }
+    }
+  }
+
+  // The import writes the countries in the dataset's order, so it could deadlock with the tests
+  // writing some of them in another order. It runs in its own schema instead.
+  #[test]
+  fn import_works() {
+    let schema = TestSchema::new();
+    let connection = schema.connection();
+    let repository = CountriesRepository::new(&connection);
+
+    assert_eq!(import(&connection), Ok(196));
+    assert_eq!(repository.count().unwrap(), 196);
+    assert_eq!(repository.find_by_alpha2_code("PL").unwrap().capital_pl, "Warszawa");
+  }
+
+  #[test]
+  fn import_is_idempotent() {
+    let schema = TestSchema::new();
+    let connection = schema.connection();
+    import(&connection).unwrap();
+
+    assert!(import(&connection).is_ok());
+    assert_eq!(CountriesRepository::new(&connection).count().unwrap(), 196);
+  }
+}
@@ -1,30 +1,321 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
-use diesel::r2d2::{Pool, ConnectionManager};
-use crate::utils::types::{DbPool, DbConnection};
+use deadpool::managed::{PoolConfig, Timeouts};
+use diesel::connection::SimpleConnection;
+use diesel::r2d2::{self, ConnectionManager, CustomizeConnection, HandleEvent, Pool};
+use diesel::r2d2::event::{CheckoutEvent, TimeoutEvent};
+use crate::utils::errors::DbError;
+use crate::utils::tls::MakeTlsConnector;
+use crate::utils::types::{AsyncDbPool, DbPool, DbConnection};
 
-const DATABASE_CONNECTION_TIMEOUT: u64 = 2048;
+// The same settings are used for both the synchronous and the async pool, apart from their
+// sizes. Together they open up to `max_size + async_max_size` connections.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PoolSettings {
+  pub max_size: u32,
+  pub async_max_size: u32,
+  pub min_idle: Option<u32>,
+  pub connection_timeout: Duration,
+  pub idle_timeout: Option<Duration>,
+  pub max_lifetime: Option<Duration>,
+  pub test_on_checkout: bool,
+  pub statement_timeout: Option<Duration>,
+}
+
+impl Default for PoolSettings {
+  fn default() -> Self {
+    Self {
+      max_size: 12,
+      // Only the sign-in is served by the async pool
+      async_max_size: 4,
+      min_idle: None,
+      connection_timeout: Duration::from_millis(2048),
+      idle_timeout: Some(Duration::from_secs(10 * 60)),
+      max_lifetime: Some(Duration::from_secs(30 * 60)),
+      test_on_checkout: true,
+      statement_timeout: None,
+    }
+  }
+}
+
+// Collected from the events of the synchronous pool, since r2d2 only keeps track of how many
+// connections it holds. Clones share the counters.
+#[derive(Clone, Default, Debug)]
+pub struct PoolMetrics {
+  counters: Arc<PoolCounters>,
+}
+
+#[derive(Default, Debug)]
+struct PoolCounters {
+  checkouts: AtomicU64,
+  timeouts: AtomicU64,
+  wait_time_micros: AtomicU64,
+  max_wait_time_micros: AtomicU64,
+}
+
+impl PoolMetrics {
+  fn record_wait(&self, wait_time: Duration) {
+    let wait_time_micros = wait_time.as_micros() as u64;
+    self.counters.wait_time_micros.fetch_add(wait_time_micros, Ordering::Relaxed);
+    self.counters.max_wait_time_micros.fetch_max(wait_time_micros, Ordering::Relaxed);
+  }
+}
+
+impl HandleEvent for PoolMetrics {
+  fn handle_checkout(&self, event: CheckoutEvent) {
+    self.counters.checkouts.fetch_add(1, Ordering::Relaxed);
+    self.record_wait(event.duration());
+  }
+
+  fn handle_timeout(&self, event: TimeoutEvent) {
+    self.counters.timeouts.fetch_add(1, Ordering::Relaxed);
+    self.record_wait(event.timeout());
+  }
+}
 
-pub fn create_database_connection_pool() -> Result<DbPool, String> {
-  let database_url = std::env::var("DATABASE_URL")
-    .map_err(|_| String::from("DATABASE_URL is not set!"))?;
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PoolStatus {
+  pub max_size: u32,
+  pub connections: u32,
+  pub idle: u32,
+  pub in_use: u32,
+  pub checkouts: u64,
+  pub timeouts: u64,
+  pub average_wait_time: Duration,
+  pub max_wait_time: Duration,
+}
+
+// deadpool doesn't report the wait times, but it knows how many requests are waiting
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AsyncPoolStatus {
+  pub max_size: u32,
+  pub connections: u32,
+  pub idle: u32,
+  pub in_use: u32,
+  pub waiting: u32,
+}
+
+#[derive(Debug)]
+struct StatementTimeout(Duration);
+
+impl CustomizeConnection<DbConnection, r2d2::Error> for StatementTimeout {
+  fn on_acquire(&self, connection: &mut DbConnection) -> Result<(), r2d2::Error> {
+    connection.batch_execute(&format!("SET statement_timeout = {}", self.0.as_millis()))
+      .map_err(r2d2::Error::QueryError)
+  }
+}
+
+pub fn create_database_connection_pool(
+  database_url: &str,
+  settings: &PoolSettings,
+  metrics: &PoolMetrics,
+) -> Result<DbPool, String> {
   let connection_manager = ConnectionManager::<DbConnection>::new(database_url);
-  let connection_pool = Pool::builder()
-    .connection_timeout(Duration::from_millis(DATABASE_CONNECTION_TIMEOUT))
-    .build(connection_manager)
+  let mut builder = Pool::builder()
+    .max_size(settings.max_size)
+    .min_idle(settings.min_idle)
+    .connection_timeout(settings.connection_timeout)
+    .idle_timeout(settings.idle_timeout)
+    .max_lifetime(settings.max_lifetime)
+    .test_on_check_out(settings.test_on_checkout)
+    .event_handler(Box::new(metrics.clone()));
+  if let Some(statement_timeout) = settings.statement_timeout {
+    builder = builder.connection_customizer(Box::new(StatementTimeout(statement_timeout)));
+  }
+  let connection_pool = builder.build(connection_manager)
     .map_err(|err| format!("Failed to create a database connection pool: {}", err))?;
 
   Ok(connection_pool)
 }
 
+// Connections are established lazily, the first time they're needed. deadpool neither keeps
+// idle connections around nor retires them, so only the size, the timeouts and the checks
+// apply to it.
+pub fn create_async_database_connection_pool(
+  database_url: &str,
+  settings: &PoolSettings,
+) -> Result<AsyncDbPool, String> {
+  let mut config = database_url.parse::<tokio_postgres::Config>()
+    .map_err(|err| format!("Failed to parse the database URL: {}", err))?;
+  if let Some(statement_timeout) = settings.statement_timeout {
+    let statement_timeout = format!("-c statement_timeout={}", statement_timeout.as_millis());
+    let options = match config.get_options() {
+      Some(options) => format!("{} {}", options, statement_timeout),
+      None => statement_timeout,
+    };
+    config.options(&options);
+  }
+  let manager_config = deadpool_postgres::ManagerConfig {
+    recycling_method: if settings.test_on_checkout {
+      deadpool_postgres::RecyclingMethod::Verified
+    } else {
+      deadpool_postgres::RecyclingMethod::Fast
+    },
+  };
+  let tls = MakeTlsConnector::new()
+    .map_err(|err| format!("Failed to set up TLS for the database connections: {}", err))?;
+  let connection_manager = deadpool_postgres::Manager::from_config(config, tls, manager_config);
+  let pool_config = PoolConfig {
+    max_size: settings.async_max_size as usize,
+    timeouts: Timeouts {
+      wait: Some(settings.connection_timeout),
+      create: Some(settings.connection_timeout),
+      recycle: Some(settings.connection_timeout),
+    },
+  };
+
+  Ok(AsyncDbPool::from_config(connection_manager, pool_config))
+}
+
+pub fn database_pool_status(pool: &DbPool, metrics: &PoolMetrics) -> PoolStatus {
+  let state = pool.state();
+  let checkouts = metrics.counters.checkouts.load(Ordering::Relaxed);
+  let timeouts = metrics.counters.timeouts.load(Ordering::Relaxed);
+  let wait_time_micros = metrics.counters.wait_time_micros.load(Ordering::Relaxed);
+  let max_wait_time_micros = metrics.counters.max_wait_time_micros.load(Ordering::Relaxed);
+
+  PoolStatus {
+    max_size: pool.max_size(),
+    connections: state.connections,
+    idle: state.idle_connections,
+    in_use: state.connections - state.idle_connections,
+    checkouts,
+    timeouts,
+    average_wait_time: Duration::from_micros(
+      wait_time_micros.checked_div(checkouts + timeouts).unwrap_or(0)
+    ),
+    max_wait_time: Duration::from_micros(max_wait_time_micros),
+  }
+}
+
+// The number of available connections goes below zero when requests are waiting for one
+pub fn async_database_pool_status(pool: &AsyncDbPool) -> AsyncPoolStatus {
+  let status = pool.status();
+  let idle = status.available.max(0) as u32;
+
+  AsyncPoolStatus {
+    max_size: status.max_size as u32,
+    connections: status.size as u32,
+    idle,
+    in_use: status.size as u32 - idle,
+    waiting: (-status.available).max(0) as u32,
+  }
+}
+
+pub fn check_database_connection(db: &DbConnection) -> Result<(), DbError> {
+  db.batch_execute("SELECT 1")?;
+
+  Ok(())
+}
+
 #[cfg(test)]
 mod tests {
+  use std::io::{Read, Write};
+  use std::net::TcpListener;
+  use std::thread;
+
   use super::*;
-  use crate::utils::test::with_db;
+  use crate::utils::test::{with_db, TestSchema, GLOBAL_SETUP};
+
+  fn database_url() -> String {
+    GLOBAL_SETUP.unwrap();
+    std::env::var("TEST_DATABASE_URL").unwrap()
+  }
 
+  // The test pools are small, so that the tests running in parallel don't use up
+  // the connections
   #[test]
   fn creating_database_connection_pool_works() {
-    with_db(|_| {
-      assert!(create_database_connection_pool().is_ok());
+    let pool = create_database_connection_pool(
+      &database_url(),
+      &TestSchema::pool_settings(),
+      &PoolMetrics::default(),
+    );
+    assert!(pool.is_ok());
+  }
+
+  #[test]
+  fn creating_async_database_connection_pool_works() {
+    let settings = PoolSettings { async_max_size: 1, ..TestSchema::pool_settings() };
+    let pool = create_async_database_connection_pool(&database_url(), &settings);
+    assert!(pool.is_ok());
+    assert_eq!(async_database_pool_status(&pool.unwrap()).max_size, 1);
+  }
+
+  // Stands in for a server which agrees to TLS, but never completes the handshake. Without
+  // a TLS connector the client would give up right after the server's answer.
+  #[test]
+  fn async_database_connection_pool_connects_over_tls() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = thread::spawn(move || {
+      let (mut stream, _) = listener.accept().unwrap();
+      let mut ssl_request = [0; 8];
+      stream.read_exact(&mut ssl_request).unwrap();
+      stream.write_all(b"S").unwrap();
+    });
+    let pool = create_async_database_connection_pool(
+      &format!("postgres://postgres@127.0.0.1:{}/mapy?sslmode=require", port),
+      &TestSchema::pool_settings(),
+    ).unwrap();
+    let mut runtime = tokio::runtime::Builder::new()
+      .basic_scheduler()
+      .enable_all()
+      .build()
+      .unwrap();
+
+    let error = runtime.block_on(pool.get()).err().unwrap().to_string();
+    server.join().unwrap();
+    assert!(error.contains("error performing TLS handshake"), "{}", error);
+    assert!(!error.contains("no TLS implementation configured"), "{}", error);
+  }
+
+  #[test]
+  fn database_pool_status_works() {
+    let schema = TestSchema::new();
+    let metrics = PoolMetrics::default();
+    let settings = PoolSettings { max_size: 2, min_idle: Some(0), ..PoolSettings::default() };
+    let pool = create_database_connection_pool(&schema.database_url(), &settings, &metrics)
+      .unwrap();
+
+    let connection = pool.get().unwrap();
+    let status = database_pool_status(&pool, &metrics);
+    assert_eq!(status.max_size, 2);
+    assert_eq!(status.connections, 1);
+    assert_eq!(status.in_use, 1);
+    assert_eq!(status.idle, 0);
+    assert_eq!(status.checkouts, 1);
+    assert_eq!(status.timeouts, 0);
+
+    drop(connection);
+    let status = database_pool_status(&pool, &metrics);
+    assert_eq!(status.in_use, 0);
+    assert_eq!(status.idle, 1);
+  }
+
+  #[test]
+  fn statement_timeout_works() {
+    let schema = TestSchema::new();
+    let settings = PoolSettings {
+      statement_timeout: Some(Duration::from_millis(10)),
+      ..TestSchema::pool_settings()
+    };
+    let pool = create_database_connection_pool(
+      &schema.database_url(),
+      &settings,
+      &PoolMetrics::default(),
+    ).unwrap();
+
+    let connection = pool.get().unwrap();
+    assert!(connection.batch_execute("SELECT pg_sleep(1)").is_err());
+    assert!(check_database_connection(&connection).is_ok());
+  }
+
+  #[test]
+  fn check_database_connection_works() {
+    with_db(|db| {
+      assert!(check_database_connection(&db).is_ok());
     });
   }
 }
@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-#[derive(PartialEq, Error, Debug)]
+#[derive(Error, Debug)]
 pub enum DbError {
   #[error("Record not found")]
   RecordNotFound,
@@ -11,7 +11,28 @@ pub enum DbError {
   #[error("Foreign key constraint violation: {0}")]
   ForeignKeyConstraintViolation(String),
   #[error(transparent)]
-  UnexpectedError(diesel::result::Error)
+  UnexpectedError(diesel::result::Error),
+  #[error(transparent)]
+  UnexpectedAsyncError(tokio_postgres::Error),
+}
+
+// tokio_postgres errors can't be compared, so they're told apart by their descriptions
+impl PartialEq for DbError {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (DbError::RecordNotFound, DbError::RecordNotFound) => true,
+      (DbError::NotFound(a, b, c), DbError::NotFound(x, y, z)) => (a, b, c) == (x, y, z),
+      (DbError::UniqueConstraintViolation(a), DbError::UniqueConstraintViolation(b)) => a == b,
+      (DbError::ForeignKeyConstraintViolation(a), DbError::ForeignKeyConstraintViolation(b)) => {
+        a == b
+      },
+      (DbError::UnexpectedError(a), DbError::UnexpectedError(b)) => a == b,
+      (DbError::UnexpectedAsyncError(a), DbError::UnexpectedAsyncError(b)) => {
+        a.to_string() == b.to_string()
+      },
+      _ => false,
+    }
+  }
 }
 
 impl From<diesel::result::Error> for DbError {
@@ -30,3 +51,37 @@ impl From<diesel::result::Error> for DbError {
     }
   }
 }
+
+impl From<tokio_postgres::Error> for DbError {
+  fn from(error: tokio_postgres::Error) -> Self {
+    use tokio_postgres::error::SqlState;
+
+    match error.code() {
+      Some(code) if *code == SqlState::UNIQUE_VIOLATION => (
+        DbError::UniqueConstraintViolation(format!("{:?}", error))
+      ),
+      Some(code) if *code == SqlState::FOREIGN_KEY_VIOLATION => (
+        DbError::ForeignKeyConstraintViolation(format!("{:?}", error))
+      ),
+      _ => DbError::UnexpectedAsyncError(error),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::error::Error;
+
+  use crate::utils::test::with_async_db;
+  use super::*;
+
+  #[test]
+  fn async_errors_keep_their_source() {
+    with_async_db(|db, _| async move {
+      let error: DbError = db.query_one("SELECT * FROM missing_table", &[]).await.unwrap_err().into();
+
+      assert!(matches!(error, DbError::UnexpectedAsyncError(_)));
+      assert!(error.source().unwrap().to_string().contains("missing_table"));
+    })
+  }
+}
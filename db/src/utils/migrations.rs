@@ -3,12 +3,16 @@ use crate::utils::types::DbConnection;
 
 embed_migrations!();
 
-pub fn run_migrations() -> Result<(), String> {
-  let database_url = std::env::var("DATABASE_URL")
-    .map_err(|_| String::from("DATABASE_URL is not set!"))?;
-  let db_conn = DbConnection::establish(&database_url)
+pub fn run_migrations(database_url: &str) -> Result<(), String> {
+  let db_conn = DbConnection::establish(database_url)
     .map_err(|err| format!("Failed to establish database connection: {}", err))?;
   embedded_migrations::run_with_output(&db_conn, &mut std::io::stdout())
     .map_err(|err| format!("Migrations failed with an error: {}", err))?;
   Ok(())
 }
+
+// Runs the migrations without any output, in whichever schema the connection is set to
+pub fn run_pending_migrations(db_conn: &DbConnection) -> Result<(), String> {
+  embedded_migrations::run(db_conn)
+    .map_err(|err| format!("Migrations failed with an error: {}", err))
+}
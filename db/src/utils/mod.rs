@@ -2,5 +2,7 @@ pub mod types;
 pub mod errors;
 pub mod migrations;
 pub mod connection_pool;
+pub mod tls;
 pub mod token;
+pub mod unit_of_work;
 pub mod test;
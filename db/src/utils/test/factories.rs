@@ -0,0 +1,114 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::models::{Organization, Session, SessionLifetimes, Teacher};
+use crate::repositories::{Repository, SessionsRepository, TeachersRepository};
+use crate::utils::types::DbConnection;
+
+// Tests running in parallel wait on each other's unique indexes, even though they can't
+// see each other's data, so every email used in a test should be unique
+pub fn unique_email() -> String {
+  format!("teacher-{}@example.com", Uuid::new_v4())
+}
+
+// Creates a teacher with the role in the organization along with a session to act as them
+pub fn sign_in_member(
+  db: &DbConnection,
+  organization: &Organization,
+  role: &str,
+) -> (Teacher, Session) {
+  let teacher = TeacherFactory::new(db).organization(organization, role).create();
+  let session = SessionFactory::new(db, &teacher).create();
+
+  (teacher, session)
+}
+
+pub struct TeacherFactory<'a> {
+  db: &'a DbConnection,
+  email: String,
+  password_digest: String,
+  organization: Option<(&'a Organization, &'a str)>,
+  deactivated: bool,
+}
+
+impl<'a> TeacherFactory<'a> {
+  pub fn new(db: &'a DbConnection) -> Self {
+    Self {
+      db,
+      email: unique_email(),
+      password_digest: String::from("digest"),
+      organization: None,
+      deactivated: false,
+    }
+  }
+
+  pub fn email(mut self, email: &str) -> Self {
+    self.email = email.to_string();
+    self
+  }
+
+  pub fn password_digest(mut self, password_digest: &str) -> Self {
+    self.password_digest = password_digest.to_string();
+    self
+  }
+
+  pub fn organization(mut self, organization: &'a Organization, role: &'a str) -> Self {
+    self.organization = Some((organization, role));
+    self
+  }
+
+  pub fn deactivated(mut self) -> Self {
+    self.deactivated = true;
+    self
+  }
+
+  pub fn create(self) -> Teacher {
+    let repository = TeachersRepository::new(self.db);
+    let mut teacher = repository.create(self.email, self.password_digest)
+      .expect("Failed to create a teacher!");
+
+    if let Some((organization, role)) = self.organization {
+      teacher = repository.join_organization(&teacher, organization, role)
+        .expect("Failed to add the teacher to the organization!");
+    }
+    if self.deactivated {
+      teacher = repository.deactivate(&teacher).expect("Failed to deactivate the teacher!");
+    }
+
+    teacher
+  }
+}
+
+pub struct SessionFactory<'a> {
+  db: &'a DbConnection,
+  owner: &'a Teacher,
+  access_token_expired: bool,
+}
+
+impl<'a> SessionFactory<'a> {
+  pub fn new(db: &'a DbConnection, owner: &'a Teacher) -> Self {
+    Self {
+      db,
+      owner,
+      access_token_expired: false,
+    }
+  }
+
+  pub fn access_token_expired(mut self) -> Self {
+    self.access_token_expired = true;
+    self
+  }
+
+  pub fn create(self) -> Session {
+    let repository = SessionsRepository::new(self.db);
+    let mut session = repository.create(self.owner, &SessionLifetimes::default())
+      .expect("Failed to create a session!");
+
+    if self.access_token_expired {
+      session.access_token_expires_at = Utc::now() - Duration::minutes(1);
+      session = repository.save(&session).expect("Failed to expire the session!");
+    }
+
+    session
+  }
+}
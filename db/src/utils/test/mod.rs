@@ -0,0 +1,186 @@
+pub mod factories;
+
+use std::future::Future;
+use std::panic::UnwindSafe;
+
+use diesel::{sql_query, Connection, RunQueryDsl};
+use diesel::connection::SimpleConnection;
+use diesel::sql_types::Text;
+use uuid::Uuid;
+
+use crate::utils::connection_pool::{
+  create_async_database_connection_pool,
+  create_database_connection_pool,
+  PoolMetrics,
+  PoolSettings,
+};
+use crate::utils::migrations::run_pending_migrations;
+use crate::utils::types::{AsyncDbConnection, AsyncDbPool, DbConnection, DbPool};
+
+lazy_static! {
+  pub static ref GLOBAL_SETUP: Option<bool> = {
+    dotenv::dotenv().ok();
+    let database_url = std::env::var("TEST_DATABASE_URL").unwrap();
+    crate::utils::migrations::run_migrations(&database_url).unwrap();
+    db_cleanup();
+    Some(true)
+  };
+}
+
+const TEST_POOL_SIZE: u32 = 2;
+
+#[derive(QueryableByName)]
+struct Name {
+  #[sql_type = "Text"]
+  name: String,
+}
+
+fn test_database_connection() -> DbConnection {
+  let database_url = std::env::var("TEST_DATABASE_URL").unwrap();
+  DbConnection::establish(&database_url).unwrap()
+}
+
+// Every test runs in its own transaction which is never committed, so the tests can't see
+// each other's data. They still wait on each other's unique keys though, so the records should
+// have unique values, e.g. the emails built by the factories.
+pub fn with_db<F>(f: F)
+where
+  F: FnOnce(DbConnection) + UnwindSafe
+{
+  GLOBAL_SETUP.unwrap();
+  let connection = test_database_connection();
+  connection.begin_test_transaction().unwrap();
+
+  let result = std::panic::catch_unwind(|| f(connection));
+  assert!(result.is_ok());
+}
+
+// A freshly migrated schema, dropped together with everything in it when it goes out of scope.
+// Connections made through its URL use it instead of the public schema, so the tests which
+// need more than one connection can't rely on test transactions and use it instead.
+pub struct TestSchema {
+  name: String,
+}
+
+impl Default for TestSchema {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl TestSchema {
+  pub fn new() -> Self {
+    GLOBAL_SETUP.unwrap();
+    let schema = Self { name: format!("test_{}", Uuid::new_v4().to_simple()) };
+    test_database_connection()
+      .batch_execute(&format!("CREATE SCHEMA {}", schema.name))
+      .expect("Failed to create the test schema!");
+    run_pending_migrations(&schema.connection()).expect("Failed to migrate the test schema!");
+
+    schema
+  }
+
+  pub fn database_url(&self) -> String {
+    let database_url = std::env::var("TEST_DATABASE_URL").unwrap();
+    let separator = if database_url.contains('?') { '&' } else { '?' };
+    format!("{}{}options=-c%20search_path%3D{}", database_url, separator, self.name)
+  }
+
+  pub fn connection(&self) -> DbConnection {
+    DbConnection::establish(&self.database_url()).unwrap()
+  }
+
+  pub fn pool_settings() -> PoolSettings {
+    PoolSettings {
+      max_size: TEST_POOL_SIZE,
+      async_max_size: TEST_POOL_SIZE,
+      ..PoolSettings::default()
+    }
+  }
+
+  pub fn db_pool(&self, metrics: &PoolMetrics) -> DbPool {
+    create_database_connection_pool(&self.database_url(), &Self::pool_settings(), metrics)
+      .unwrap()
+  }
+
+  pub fn async_db_pool(&self) -> AsyncDbPool {
+    create_async_database_connection_pool(&self.database_url(), &Self::pool_settings()).unwrap()
+  }
+
+  // Makes every write to the table fail, e.g. for checking that the writes made before it
+  // are rolled back. Creating the trigger locks the table, so it's only available in a test
+  // schema, where no other test can be waiting for it.
+  pub fn fail_writes_to(&self, table: &str) {
+    self.connection()
+      .batch_execute(&format!(
+        "CREATE OR REPLACE FUNCTION fail_write() RETURNS trigger AS $$
+        BEGIN
+          RAISE EXCEPTION 'Writing to % has failed', TG_TABLE_NAME;
+        END
+        $$ LANGUAGE plpgsql;
+        CREATE TRIGGER fail_writes_to_{table} BEFORE INSERT OR UPDATE OR DELETE ON {table}
+        FOR EACH ROW EXECUTE PROCEDURE fail_write();",
+        table = table,
+      ))
+      .expect("Failed to make the writes fail!");
+  }
+}
+
+impl Drop for TestSchema {
+  fn drop(&mut self) {
+    test_database_connection()
+      .batch_execute(&format!("DROP SCHEMA {} CASCADE", self.name))
+      .expect("Failed to drop the test schema!");
+  }
+}
+
+// The async and the synchronous connection can't share a transaction, so both of them use
+// a test schema. The synchronous connection is there for the test setup.
+pub fn with_async_db<F, Fut>(f: F)
+where
+  F: FnOnce(AsyncDbConnection, DbConnection) -> Fut + UnwindSafe,
+  Fut: Future<Output = ()>,
+{
+  let schema = TestSchema::new();
+
+  let result = std::panic::catch_unwind(|| {
+    let mut runtime = tokio::runtime::Builder::new()
+      .basic_scheduler()
+      .enable_all()
+      .build()
+      .unwrap();
+
+    runtime.block_on(async {
+      let async_connection = schema.async_db_pool().get().await.unwrap();
+      f(async_connection, schema.connection()).await
+    })
+  });
+
+  drop(schema);
+  assert!(result.is_ok());
+}
+
+// Removes whatever has been committed to the test database, e.g. by the benchmarks or by
+// the schemas of the async tests which have been interrupted. The tables are looked up
+// in the catalog, so that the new ones don't have to be listed here.
+pub fn db_cleanup() {
+  let connection = test_database_connection();
+
+  let schemas = sql_query(
+    "SELECT nspname AS name FROM pg_namespace WHERE nspname LIKE 'test\\_%'"
+  ).load::<Name>(&connection).expect("Failed to look up test schemas!");
+  for schema in schemas {
+    connection.batch_execute(&format!("DROP SCHEMA {} CASCADE", schema.name))
+      .expect("Failed to drop a test schema!");
+  }
+
+  let tables = sql_query(
+    "SELECT tablename AS name FROM pg_tables
+    WHERE schemaname = current_schema() AND tablename <> '__diesel_schema_migrations'"
+  ).load::<Name>(&connection).expect("Failed to look up tables!");
+  if !tables.is_empty() {
+    let names: Vec<String> = tables.into_iter().map(|table| table.name).collect();
+    connection.batch_execute(&format!("TRUNCATE {} RESTART IDENTITY CASCADE", names.join(", ")))
+      .expect("Failed to clean up tables!");
+  }
+}
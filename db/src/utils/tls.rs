@@ -0,0 +1,105 @@
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_postgres::tls::{self, ChannelBinding, MakeTlsConnect, TlsConnect};
+
+// Lets the async pool connect over TLS the same way libpq does for the synchronous one, so that
+// the "sslmode" of the database URL is honoured. With the default "prefer" mode the connection
+// falls back to plaintext when the server doesn't support TLS.
+#[derive(Clone)]
+pub struct MakeTlsConnector(native_tls::TlsConnector);
+
+impl MakeTlsConnector {
+  pub fn new() -> Result<Self, native_tls::Error> {
+    Ok(Self(native_tls::TlsConnector::new()?))
+  }
+}
+
+impl<S> MakeTlsConnect<S> for MakeTlsConnector
+where
+  S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+  type Stream = TlsStream<S>;
+  type TlsConnect = TlsConnector;
+  type Error = native_tls::Error;
+
+  fn make_tls_connect(&mut self, domain: &str) -> Result<TlsConnector, native_tls::Error> {
+    Ok(TlsConnector {
+      connector: self.0.clone().into(),
+      domain: domain.to_string(),
+    })
+  }
+}
+
+pub struct TlsConnector {
+  connector: tokio_tls::TlsConnector,
+  domain: String,
+}
+
+impl<S> TlsConnect<S> for TlsConnector
+where
+  S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+  type Stream = TlsStream<S>;
+  type Error = native_tls::Error;
+  #[allow(clippy::type_complexity)]
+  type Future = Pin<Box<dyn Future<Output = Result<TlsStream<S>, native_tls::Error>> + Send>>;
+
+  fn connect(self, stream: S) -> Self::Future {
+    Box::pin(async move {
+      let stream = self.connector.connect(&self.domain, stream).await?;
+
+      Ok(TlsStream(stream))
+    })
+  }
+}
+
+pub struct TlsStream<S>(tokio_tls::TlsStream<S>);
+
+impl<S> AsyncRead for TlsStream<S>
+where
+  S: AsyncRead + AsyncWrite + Unpin,
+{
+  fn poll_read(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &mut [u8],
+  ) -> Poll<io::Result<usize>> {
+    Pin::new(&mut self.0).poll_read(cx, buf)
+  }
+}
+
+impl<S> AsyncWrite for TlsStream<S>
+where
+  S: AsyncRead + AsyncWrite + Unpin,
+{
+  fn poll_write(
+    mut self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &[u8],
+  ) -> Poll<io::Result<usize>> {
+    Pin::new(&mut self.0).poll_write(cx, buf)
+  }
+
+  fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    Pin::new(&mut self.0).poll_flush(cx)
+  }
+
+  fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    Pin::new(&mut self.0).poll_shutdown(cx)
+  }
+}
+
+impl<S> tls::TlsStream for TlsStream<S>
+where
+  S: AsyncRead + AsyncWrite + Unpin,
+{
+  // tokio-tls doesn't expose the server's certificate, so SCRAM falls back to the variant
+  // without channel binding
+  fn channel_binding(&self) -> ChannelBinding {
+    ChannelBinding::none()
+  }
+}
@@ -3,3 +3,6 @@ use diesel::PgConnection;
 
 pub type DbConnection = PgConnection;
 pub type DbPool = Pool<ConnectionManager<DbConnection>>;
+
+pub type AsyncDbConnection = deadpool_postgres::Client;
+pub type AsyncDbPool = deadpool_postgres::Pool;
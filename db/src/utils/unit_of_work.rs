@@ -0,0 +1,256 @@
+use diesel::Connection;
+
+use crate::utils::errors::DbError;
+use crate::utils::types::{AsyncDbConnection, DbConnection};
+use crate::repositories::Repository;
+use crate::async_repositories::AsyncDbClient;
+
+#[derive(PartialEq, Debug)]
+pub enum UnitOfWorkError<E> {
+  // The closure has failed and everything it has written has been rolled back
+  Aborted(E),
+  // Beginning, committing or rolling back the transaction has failed
+  DbError(DbError),
+}
+
+impl<E> From<diesel::result::Error> for UnitOfWorkError<E> {
+  fn from(error: diesel::result::Error) -> Self {
+    UnitOfWorkError::DbError(error.into())
+  }
+}
+
+impl<E> From<tokio_postgres::Error> for UnitOfWorkError<E> {
+  fn from(error: tokio_postgres::Error) -> Self {
+    UnitOfWorkError::DbError(error.into())
+  }
+}
+
+// Groups the writes of a service, so that either all or none of them are persisted
+pub struct UnitOfWork<'a> {
+  db: &'a DbConnection,
+}
+
+impl<'a> UnitOfWork<'a> {
+  // Runs the closure in a transaction, which is committed only if the closure succeeds.
+  // When already inside of a transaction, a savepoint is used instead.
+  pub fn run<T, E, F>(db: &'a DbConnection, f: F) -> Result<T, UnitOfWorkError<E>>
+  where
+    F: FnOnce(&UnitOfWork<'a>) -> Result<T, E>,
+  {
+    let unit_of_work = UnitOfWork { db };
+
+    db.transaction(|| f(&unit_of_work).map_err(UnitOfWorkError::Aborted))
+  }
+
+  pub fn repository<R: Repository<'a, Db = DbConnection>>(&self) -> R {
+    R::new(self.db)
+  }
+
+  pub fn connection(&self) -> &'a DbConnection {
+    self.db
+  }
+}
+
+// The async counterpart of `UnitOfWork`. The transaction is begun and finished explicitly, as
+// a closure can't return a future borrowing its argument. It's rolled back when dropped, e.g.
+// when the request is cancelled, unless it has been finished.
+pub struct AsyncUnitOfWork<'a> {
+  transaction: deadpool_postgres::Transaction<'a>,
+}
+
+impl<'a> AsyncUnitOfWork<'a> {
+  pub async fn begin(db: &'a mut AsyncDbConnection) -> Result<AsyncUnitOfWork<'a>, DbError> {
+    Ok(Self { transaction: db.transaction().await? })
+  }
+
+  pub fn repository<'b, R: Repository<'b, Db = dyn AsyncDbClient + 'b>>(&'b self) -> R {
+    R::new(self.connection())
+  }
+
+  pub fn connection(&self) -> &dyn AsyncDbClient {
+    &self.transaction
+  }
+
+  // Commits the transaction if the service has succeeded, rolls it back otherwise
+  pub async fn finish<T, E>(self, result: Result<T, E>) -> Result<T, UnitOfWorkError<E>> {
+    match result {
+      Ok(value) => {
+        self.transaction.commit().await?;
+        Ok(value)
+      },
+      Err(error) => {
+        self.transaction.rollback().await?;
+        Err(UnitOfWorkError::Aborted(error))
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::async_repositories::AsyncSessionsRepository;
+  use crate::models::SessionLifetimes;
+  use crate::repositories::{SessionsRepository, TeachersRepository};
+  use crate::utils::test::factories::{unique_email, TeacherFactory};
+  use crate::utils::test::{with_async_db, with_db, TestSchema};
+  use super::*;
+
+  async fn count_sessions(db: &AsyncDbConnection) -> i64 {
+    let query = "SELECT COUNT(*) FROM sessions";
+    tokio_postgres::Client::query_one(db, query, &[]).await.unwrap().get(0)
+  }
+
+  #[test]
+  fn run_commits_when_closure_succeeds() {
+    with_db(|connection| {
+      let result = UnitOfWork::run(&connection, |unit_of_work| {
+        let teacher = unit_of_work.repository::<TeachersRepository>()
+          .create(unique_email(), "test".into())?;
+        unit_of_work.repository::<SessionsRepository>()
+          .create(&teacher, &SessionLifetimes::default())
+      });
+
+      assert!(result.is_ok());
+      assert_eq!(TeachersRepository::new(&connection).count().unwrap(), 1);
+      assert_eq!(SessionsRepository::new(&connection).count().unwrap(), 1);
+    })
+  }
+
+  #[test]
+  fn run_rolls_back_when_closure_fails() {
+    with_db(|connection| {
+      let result: Result<(), _> = UnitOfWork::run(&connection, |unit_of_work| {
+        unit_of_work.repository::<TeachersRepository>()
+          .create(unique_email(), "test".into())
+          .unwrap();
+
+        Err("service error")
+      });
+
+      assert_eq!(result, Err(UnitOfWorkError::Aborted("service error")));
+      assert_eq!(TeachersRepository::new(&connection).count().unwrap(), 0);
+    })
+  }
+
+  #[test]
+  fn run_rolls_back_when_a_later_write_fails() {
+    with_db(|connection| {
+      let email = unique_email();
+      let result = UnitOfWork::run(&connection, |unit_of_work| {
+        let repository = unit_of_work.repository::<TeachersRepository>();
+        repository.create(email.clone(), "test".into())?;
+        repository.create(unique_email(), "test".into())?;
+        // Violates the unique constraint on the email
+        repository.create(email.clone(), "test".into())
+      });
+
+      assert!(matches!(
+        result,
+        Err(UnitOfWorkError::Aborted(DbError::UniqueConstraintViolation(_)))
+      ));
+      assert_eq!(TeachersRepository::new(&connection).count().unwrap(), 0);
+    })
+  }
+
+  // `with_db` runs the tests above in a transaction, so they only exercise savepoints. These run
+  // on their own schema, where the unit of work commits or rolls back a real transaction, and
+  // check the outcome from another connection.
+  #[test]
+  fn run_commits_the_transaction() {
+    let schema = TestSchema::new();
+
+    let result = UnitOfWork::run(&schema.connection(), |unit_of_work| {
+      unit_of_work.repository::<TeachersRepository>().create(unique_email(), "test".into())
+    });
+
+    assert!(result.is_ok());
+    assert_eq!(TeachersRepository::new(&schema.connection()).count().unwrap(), 1);
+  }
+
+  #[test]
+  fn run_rolls_back_the_transaction() {
+    let schema = TestSchema::new();
+
+    let result: Result<(), _> = UnitOfWork::run(&schema.connection(), |unit_of_work| {
+      unit_of_work.repository::<TeachersRepository>()
+        .create(unique_email(), "test".into())
+        .unwrap();
+
+      Err("service error")
+    });
+
+    assert_eq!(result, Err(UnitOfWorkError::Aborted("service error")));
+    assert_eq!(TeachersRepository::new(&schema.connection()).count().unwrap(), 0);
+  }
+
+  #[test]
+  fn nested_run_rolls_back_only_its_own_writes() {
+    with_db(|connection| {
+      let result = UnitOfWork::run(&connection, |unit_of_work| {
+        let repository = unit_of_work.repository::<TeachersRepository>();
+        repository.create(unique_email(), "test".into())?;
+
+        let nested_result: Result<(), _> = UnitOfWork::run(unit_of_work.connection(), |nested| {
+          nested.repository::<TeachersRepository>()
+            .create(unique_email(), "test".into())?;
+          Err(DbError::RecordNotFound)
+        });
+        assert_eq!(nested_result, Err(UnitOfWorkError::Aborted(DbError::RecordNotFound)));
+
+        repository.count()
+      });
+
+      assert_eq!(result, Ok(1));
+      assert_eq!(TeachersRepository::new(&connection).count().unwrap(), 1);
+    })
+  }
+
+  #[test]
+  fn async_finish_commits_when_service_succeeds() {
+    with_async_db(|mut db, connection| async move {
+      let teacher = TeacherFactory::new(&connection).create();
+
+      let unit_of_work = AsyncUnitOfWork::begin(&mut db).await.unwrap();
+      let session = unit_of_work.repository::<AsyncSessionsRepository>()
+        .create(&teacher, &SessionLifetimes::default())
+        .await;
+      let result: Result<_, UnitOfWorkError<()>> = unit_of_work.finish(Ok(session.unwrap())).await;
+
+      assert!(result.is_ok());
+      assert_eq!(count_sessions(&db).await, 1);
+    })
+  }
+
+  #[test]
+  fn async_finish_rolls_back_when_service_fails() {
+    with_async_db(|mut db, connection| async move {
+      let teacher = TeacherFactory::new(&connection).create();
+
+      let unit_of_work = AsyncUnitOfWork::begin(&mut db).await.unwrap();
+      unit_of_work.repository::<AsyncSessionsRepository>()
+        .create(&teacher, &SessionLifetimes::default())
+        .await
+        .unwrap();
+      let result: Result<(), _> = unit_of_work.finish(Err("service error")).await;
+
+      assert_eq!(result, Err(UnitOfWorkError::Aborted("service error")));
+      assert_eq!(count_sessions(&db).await, 0);
+    })
+  }
+
+  #[test]
+  fn async_unit_of_work_rolls_back_when_dropped() {
+    with_async_db(|mut db, connection| async move {
+      let teacher = TeacherFactory::new(&connection).create();
+
+      let unit_of_work = AsyncUnitOfWork::begin(&mut db).await.unwrap();
+      unit_of_work.repository::<AsyncSessionsRepository>()
+        .create(&teacher, &SessionLifetimes::default())
+        .await
+        .unwrap();
+      drop(unit_of_work);
+
+      assert_eq!(count_sessions(&db).await, 0);
+    })
+  }
+}
@@ -0,0 +1,47 @@
+# Messages are looked up by "<field>-<code>" first, then by "<code>" alone,
+# which receives the name of the offending field as $field.
+
+## Generic errors
+
+missing = { $field } is missing
+unauthorized = Unauthorized
+forbidden = Forbidden
+not_found = Not found
+unexpected_error = Unexpected error has occurred
+
+## Request body
+
+body-malformed_json = Request body is not valid JSON
+body-invalid_type = Request body contains a value of invalid type
+body-invalid_content_type = Content type must be JSON
+body-too_large = Request body is too large
+body-unreadable = Request body couldn't be read
+
+## Teachers and sessions
+
+email-blank = Email can't be blank
+email-invalid = Email is invalid
+password-blank = Password can't be blank
+password-too_short = Password is too short (minimum is 8 characters)
+password-too_long = Password is too long (maximum is 128 characters)
+base-invalid_credentials = Invalid email/password combination
+base-deactivated = This account has been deactivated
+session_uuid-blank = Session UUID can't be blank
+refresh_token-blank = Refresh token can't be blank
+
+## Organizations
+
+name-blank = Name can't be blank
+role-invalid = Role is invalid
+organization_uuid-blank = Organization UUID can't be blank
+teacher_uuid-blank = Teacher UUID can't be blank
+teacher_uuid-self_deactivation = You can't deactivate your own account
+token-blank = Invitation token can't be blank
+token-expired = Invitation has expired
+token-already_accepted = Invitation has already been accepted
+base-already_in_organization = You already belong to an organization
+
+## Countries
+
+code-blank = Country code can't be blank
+continent-invalid = Continent is invalid
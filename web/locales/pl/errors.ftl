@@ -0,0 +1,47 @@
+# Messages are looked up by "<field>-<code>" first, then by "<code>" alone,
+# which receives the name of the offending field as $field.
+
+## Generic errors
+
+missing = Brakuje pola { $field }
+unauthorized = Brak autoryzacji
+forbidden = Brak uprawnień
+not_found = Nie znaleziono
+unexpected_error = Wystąpił nieoczekiwany błąd
+
+## Request body
+
+body-malformed_json = Treść żądania nie jest poprawnym JSON-em
+body-invalid_type = Treść żądania zawiera wartość nieprawidłowego typu
+body-invalid_content_type = Treść żądania musi być w formacie JSON
+body-too_large = Treść żądania jest za duża
+body-unreadable = Nie udało się odczytać treści żądania
+
+## Teachers and sessions
+
+email-blank = Adres e-mail nie może być pusty
+email-invalid = Adres e-mail jest nieprawidłowy
+password-blank = Hasło nie może być puste
+password-too_short = Hasło jest za krótkie (minimum to 8 znaków)
+password-too_long = Hasło jest za długie (maksimum to 128 znaków)
+base-invalid_credentials = Nieprawidłowy adres e-mail lub hasło
+base-deactivated = To konto zostało dezaktywowane
+session_uuid-blank = UUID sesji nie może być pusty
+refresh_token-blank = Token odświeżania nie może być pusty
+
+## Organizations
+
+name-blank = Nazwa nie może być pusta
+role-invalid = Rola jest nieprawidłowa
+organization_uuid-blank = UUID organizacji nie może być pusty
+teacher_uuid-blank = UUID nauczyciela nie może być pusty
+teacher_uuid-self_deactivation = Nie możesz dezaktywować własnego konta
+token-blank = Token zaproszenia nie może być pusty
+token-expired = Zaproszenie wygasło
+token-already_accepted = Zaproszenie zostało już przyjęte
+base-already_in_organization = Należysz już do organizacji
+
+## Countries
+
+code-blank = Kod kraju nie może być pusty
+continent-invalid = Kontynent jest nieprawidłowy
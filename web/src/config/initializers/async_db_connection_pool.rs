@@ -0,0 +1,7 @@
+use db::prelude::{create_async_database_connection_pool, AsyncDbPool, PoolSettings};
+
+pub fn init(database_url: &str, settings: &PoolSettings) -> Result<AsyncDbPool, String> {
+  let pool = create_async_database_connection_pool(database_url, settings)?;
+
+  Ok(pool)
+}
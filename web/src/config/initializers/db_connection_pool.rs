@@ -1,7 +1,8 @@
-use db::prelude::{create_database_connection_pool, DbPool};
+use db::prelude::{create_database_connection_pool, DbPool, PoolMetrics, PoolSettings};
 
-pub fn init() -> Result<DbPool, String> {
-  let pool = create_database_connection_pool()?;
+pub fn init(database_url: &str, settings: &PoolSettings) -> Result<(DbPool, PoolMetrics), String> {
+  let metrics = PoolMetrics::default();
+  let pool = create_database_connection_pool(database_url, settings, &metrics)?;
 
-  Ok(pool)
+  Ok((pool, metrics))
 }
@@ -1,7 +1,7 @@
 use db::prelude::run_migrations;
 
-pub fn init() -> Result<(), String> {
-  run_migrations()?;
+pub fn init(database_url: &str) -> Result<(), String> {
+  run_migrations(database_url)?;
 
   Ok(())
 }
@@ -1,19 +1,23 @@
+mod async_db_connection_pool;
 mod db_connection_pool;
 mod environment;
 mod logger;
 mod migrations;
-mod port;
 mod rollbar;
+mod settings;
 
-use crate::prelude::DbPool;
+use crate::prelude::{AsyncDbPool, DbPool, PoolMetrics, Settings};
 
-pub fn run() -> Result<(String, DbPool), String> {
+pub fn run() -> Result<(Settings, DbPool, PoolMetrics, AsyncDbPool), String> {
   environment::init();
+  let settings = settings::init()?;
   logger::init()?;
-  rollbar::init();
-  migrations::init()?;
-  let db_pool = db_connection_pool::init()?;
-  let port = port::init();
+  rollbar::init(&settings.rollbar);
+  migrations::init(&settings.database_url)?;
+  let (db_pool, db_pool_metrics) =
+    db_connection_pool::init(&settings.database_url, &settings.database_pool)?;
+  let async_db_pool =
+    async_db_connection_pool::init(&settings.database_url, &settings.database_pool)?;
 
-  Ok((port, db_pool))
+  Ok((settings, db_pool, db_pool_metrics, async_db_pool))
 }
@@ -1,13 +1,22 @@
 use backtrace;
 
-use app::prelude::ROLLBAR_CLIENT;
-
-pub fn init() {
-  std::panic::set_hook(Box::new(move |panic_info| {
-    let backtrace = backtrace::Backtrace::new();
-    ROLLBAR_CLIENT.build_report()
-      .from_panic(panic_info)
-      .with_backtrace(&backtrace)
-      .send();
-  }));
+use app::prelude::{configure_rollbar, ROLLBAR_CLIENT};
+
+use crate::config::settings::RollbarSettings;
+
+// Without Rollbar the default panic hook, printing to stderr, stays in place
+pub fn init(settings: &Option<RollbarSettings>) {
+  if let Some(settings) = settings {
+    configure_rollbar(settings.access_token.clone(), settings.environment.clone());
+
+    std::panic::set_hook(Box::new(move |panic_info| {
+      if let Some(client) = ROLLBAR_CLIENT.get() {
+        let backtrace = backtrace::Backtrace::new();
+        client.build_report()
+          .from_panic(panic_info)
+          .with_backtrace(&backtrace)
+          .send();
+      }
+    }));
+  }
 }
@@ -0,0 +1,7 @@
+use crate::config::settings::Settings;
+
+pub fn init() -> Result<Settings, String> {
+  let settings = Settings::load()?;
+
+  Ok(settings)
+}
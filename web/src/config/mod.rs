@@ -1,2 +1,4 @@
 pub mod initializers;
+pub mod openapi;
 pub mod routes;
+pub mod settings;
@@ -0,0 +1,106 @@
+use lazy_static::lazy_static;
+use serde_json::Value;
+
+use crate::controllers::countries;
+use crate::controllers::docs;
+use crate::controllers::organizations;
+use crate::controllers::status;
+use crate::controllers::teachers;
+use crate::utils::openapi::ApiSpec;
+
+lazy_static! {
+  // The spec only depends on the code, so it's built once, on the first request for it
+  static ref SPEC: Value = build().into_json();
+}
+
+pub fn spec() -> &'static Value {
+  &SPEC
+}
+
+// Mirrors `routes::config`, every controller describes the routes it registers
+
+fn build() -> ApiSpec {
+  let mut spec = ApiSpec::new();
+
+  status::openapi(&mut spec);
+  docs::openapi(&mut spec);
+  countries::openapi(&mut spec);
+  teachers::openapi(&mut spec);
+  organizations::openapi(&mut spec);
+
+  spec
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{dev::Service, http::{HeaderName, HeaderValue, Method, StatusCode}, test, App};
+
+  use crate::config::routes;
+  use super::*;
+
+  const MATCHED_PATTERN_HEADER: &str = "x-matched-pattern";
+
+  fn route_table() -> Vec<(String, String)> {
+    let mut table = vec![];
+    App::new().configure(|cfg| routes::register(cfg, &mut table));
+
+    let mut routes: Vec<(String, String)> = table.into_iter()
+      .map(|(method, path)| (method.to_string(), path))
+      .collect();
+    routes.sort();
+    routes
+  }
+
+  #[actix_rt::test]
+  async fn every_operation_in_the_spec_is_routed() {
+    // Reports which route pattern (if any) the request has been matched against
+    let mut app = test::init_service(
+      App::new()
+        .configure(routes::config)
+        .wrap_fn(|request, service| {
+          let pattern = request.match_pattern();
+          let response = service.call(request);
+
+          async move {
+            let mut response = response.await?;
+            if let Some(pattern) = pattern {
+              response.headers_mut().insert(
+                HeaderName::from_static(MATCHED_PATTERN_HEADER),
+                HeaderValue::from_str(&pattern).unwrap(),
+              );
+            }
+            Ok(response)
+          }
+        })
+    ).await;
+
+    for (method, path) in build().operations() {
+      let uri = path.split('/')
+        .map(|segment| if segment.starts_with('{') { "placeholder" } else { segment })
+        .collect::<Vec<&str>>()
+        .join("/");
+      let request = test::TestRequest::default()
+        .method(Method::from_bytes(method.as_bytes()).unwrap())
+        .uri(&format!("/api/v1{}", uri))
+        .to_request();
+      let response = test::call_service(&mut app, request).await;
+
+      assert_ne!(response.status(), StatusCode::METHOD_NOT_ALLOWED, "{} {}", method, path);
+      assert_eq!(
+        response.headers().get(MATCHED_PATTERN_HEADER).and_then(|value| value.to_str().ok()),
+        Some(format!("/api/v1{}", path).as_str()),
+        "{} {}",
+        method,
+        path,
+      );
+    }
+  }
+
+  #[test]
+  fn every_route_is_described_in_the_spec() {
+    let mut operations = build().operations();
+    operations.sort();
+
+    assert_eq!(route_table(), operations);
+  }
+}
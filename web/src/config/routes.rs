@@ -1,15 +1,39 @@
-use actix_web::web;
+use actix_web::{dev::Service, http::Method, web};
 
+use crate::controllers::countries;
+use crate::controllers::docs;
+use crate::controllers::organizations;
 use crate::controllers::status;
 use crate::controllers::teachers;
+use crate::utils::errors::{json_error_handler, localize_error_response};
+use crate::utils::locale::Locale;
+use crate::utils::router::Router;
 
 pub fn config(cfg: &mut web::ServiceConfig) {
+  register(cfg, &mut vec![]);
+}
+
+// Same as `config`, and lists the registered routes in `table`, relative to "/api/v1"
+pub fn register(cfg: &mut web::ServiceConfig, table: &mut Vec<(Method, String)>) {
+  cfg.app_data(web::JsonConfig::default().error_handler(json_error_handler));
   cfg.service(
     web::scope("/api")
+      .wrap_fn(|request, service| {
+        let locale = Locale::from_headers(request.headers());
+        let response = service.call(request);
+
+        async move { Ok(localize_error_response(response.await?, locale)) }
+      })
       .service(
-        web::scope("/v1")
-          .configure(status::config)
-          .configure(teachers::config)
+        web::scope("/v1").configure(|cfg| {
+          let mut router = Router::new(cfg, table);
+
+          status::config(&mut router);
+          docs::config(&mut router);
+          countries::config(&mut router);
+          teachers::config(&mut router);
+          organizations::config(&mut router);
+        })
       )
   );
 }
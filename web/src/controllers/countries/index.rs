@@ -0,0 +1,60 @@
+use app::services::countries::list;
+
+use crate::prelude::*;
+use crate::serializers::CountrySerializer;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct Params {
+  continent: Option<String>,
+}
+
+pub async fn handler(
+  db_pool: web::Data<DbPool>,
+  params: web::Query<Params>,
+) -> Result<HttpResponse, ApiError> {
+  let db = db_connect!(db_pool);
+  let params = params.into_inner();
+
+  let countries = web::block(move || list(params.continent, &db)).await?;
+
+  Ok(http_200!(countries.iter().map(CountrySerializer::from).collect::<Vec<CountrySerializer>>()))
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::StatusCode, test::TestRequest};
+
+  use db::seeds::countries::import;
+  use crate::utils::test::test_app;
+
+  #[actix_rt::test]
+  async fn index_works() {
+    let mut app = test_app().await;
+    let count = import(&app.db()).unwrap();
+
+    let response = app.call(TestRequest::get().uri("/api/v1/countries")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body.as_array().unwrap().len(), count);
+  }
+
+  #[actix_rt::test]
+  async fn index_filters_by_continent() {
+    let mut app = test_app().await;
+    import(&app.db()).unwrap();
+
+    let response = app.call(TestRequest::get().uri("/api/v1/countries?continent=europe")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let countries = response.body.as_array().unwrap();
+    assert!(!countries.is_empty());
+    assert!(countries.iter().all(|country| country["continent"] == "europe"));
+  }
+
+  #[actix_rt::test]
+  async fn index_fails_when_continent_is_invalid() {
+    let mut app = test_app().await;
+
+    let response = app.call(TestRequest::get().uri("/api/v1/countries?continent=atlantis")).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.errors(), vec![("continent", "invalid")]);
+  }
+}
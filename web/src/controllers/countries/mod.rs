@@ -0,0 +1,25 @@
+mod index;
+mod show;
+
+use crate::prelude::*;
+use crate::serializers::CountrySerializer;
+
+pub fn config(router: &mut Router) {
+  router.scope("/countries", |router| {
+    router
+      .route("", Method::GET, index::handler)
+      .route("/{code}", Method::GET, show::handler);
+  });
+}
+
+pub fn openapi(spec: &mut ApiSpec) {
+  spec.operation("GET", "/countries", "List countries")
+    .query_param("continent", "One of the continents, e.g. \"europe\"")
+    .response::<Vec<CountrySerializer>>(200, "Countries ordered by their alpha-2 code")
+    .error(400, "Invalid continent");
+
+  spec.operation("GET", "/countries/{code}", "Show a country")
+    .path_param("code", "ISO 3166-1 alpha-2 code, case insensitive")
+    .response::<CountrySerializer>(200, "The country")
+    .error(404, "Country not found");
+}
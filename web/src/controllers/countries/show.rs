@@ -0,0 +1,42 @@
+use app::services::countries::find;
+
+use crate::prelude::*;
+use crate::serializers::CountrySerializer;
+
+pub async fn handler(
+  web::Path(code): web::Path<String>,
+  db_pool: web::Data<DbPool>,
+) -> Result<HttpResponse, ApiError> {
+  let db = db_connect!(db_pool);
+
+  let country = web::block(move || find(code, &db)).await?;
+
+  Ok(http_200!(CountrySerializer::from(&country)))
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::StatusCode, test::TestRequest};
+
+  use db::seeds::countries::import;
+  use crate::utils::test::test_app;
+
+  #[actix_rt::test]
+  async fn show_works() {
+    let mut app = test_app().await;
+    import(&app.db()).unwrap();
+
+    let response = app.call(TestRequest::get().uri("/api/v1/countries/pl")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["alpha2_code"], "PL");
+  }
+
+  #[actix_rt::test]
+  async fn show_fails_when_country_doesnt_exist() {
+    let mut app = test_app().await;
+
+    let response = app.call(TestRequest::get().uri("/api/v1/countries/xx")).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+    assert_eq!(response.errors(), vec![("base", "not_found")]);
+  }
+}
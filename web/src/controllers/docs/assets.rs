@@ -0,0 +1,54 @@
+use crate::prelude::*;
+
+// The docs UI is bundled into the binary, so that it works offline and under a strict CSP
+pub async fn handler(web::Path(name): web::Path<String>) -> Result<HttpResponse, ApiError> {
+  let (content_type, body): (&str, &'static [u8]) = match name.as_str() {
+    "docs.js" => {
+      ("application/javascript; charset=utf-8", include_bytes!("../../../static/docs.js"))
+    },
+    "swagger-ui-bundle.js" => (
+      "application/javascript; charset=utf-8",
+      include_bytes!("../../../static/swagger-ui/swagger-ui-bundle.js"),
+    ),
+    "swagger-ui.css" => {
+      ("text/css; charset=utf-8", include_bytes!("../../../static/swagger-ui/swagger-ui.css"))
+    },
+    _ => return Err(ApiError::NotFound),
+  };
+
+  Ok(HttpResponse::Ok().content_type(content_type).body(body))
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::{header, StatusCode}, test::TestRequest};
+
+  use crate::utils::test::test_app;
+
+  #[actix_rt::test]
+  async fn assets_work() {
+    let mut app = test_app().await;
+
+    let response = app.call(TestRequest::get().uri("/api/v1/docs/swagger-ui-bundle.js")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(
+      response.headers.get(header::CONTENT_TYPE).unwrap(),
+      "application/javascript; charset=utf-8",
+    );
+
+    let response = app.call(TestRequest::get().uri("/api/v1/docs/swagger-ui.css")).await;
+    assert_eq!(response.status, StatusCode::OK);
+
+    let response = app.call(TestRequest::get().uri("/api/v1/docs/docs.js")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(response.body.as_str().unwrap().contains("/api/v1/openapi.json"));
+  }
+
+  #[actix_rt::test]
+  async fn assets_fail_when_asset_doesnt_exist() {
+    let mut app = test_app().await;
+
+    let response = app.call(TestRequest::get().uri("/api/v1/docs/docs.html")).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+  }
+}
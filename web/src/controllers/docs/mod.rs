@@ -0,0 +1,25 @@
+mod assets;
+mod openapi;
+mod ui;
+
+use crate::prelude::*;
+
+pub fn config(router: &mut Router) {
+  router
+    .route("/openapi.json", Method::GET, openapi::handler)
+    .route("/docs", Method::GET, ui::handler)
+    .route("/docs/{asset}", Method::GET, assets::handler);
+}
+
+pub fn openapi(spec: &mut ApiSpec) {
+  spec.operation("GET", "/openapi.json", "Show this OpenAPI specification")
+    .response::<serde_json::Value>(200, "OpenAPI 3 document");
+
+  spec.operation("GET", "/docs", "Browse this OpenAPI specification")
+    .html_response(200, "HTML page rendering the specification with Swagger UI");
+
+  spec.operation("GET", "/docs/{asset}", "Show a script or a stylesheet of the docs page")
+    .path_param("asset", "File name, e.g. \"swagger-ui.css\"")
+    .file_response(200, "The JavaScript or the CSS file")
+    .error(404, "Asset not found");
+}
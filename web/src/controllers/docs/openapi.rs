@@ -0,0 +1,22 @@
+use crate::config::openapi;
+use crate::prelude::*;
+
+pub async fn handler() -> impl Responder {
+  HttpResponse::Ok().json(openapi::spec())
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::StatusCode, test::TestRequest};
+
+  use crate::utils::test::test_app;
+
+  #[actix_rt::test]
+  async fn openapi_works() {
+    let mut app = test_app().await;
+
+    let response = app.call(TestRequest::get().uri("/api/v1/openapi.json")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["openapi"], "3.0.3");
+  }
+}
@@ -0,0 +1,25 @@
+use crate::prelude::*;
+
+pub async fn handler() -> impl Responder {
+  HttpResponse::Ok()
+    .content_type("text/html; charset=utf-8")
+    .body(include_str!("../../../static/docs.html"))
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::StatusCode, test::TestRequest};
+
+  use crate::utils::test::test_app;
+
+  #[actix_rt::test]
+  async fn ui_works() {
+    let mut app = test_app().await;
+
+    let response = app.call(TestRequest::get().uri("/api/v1/docs")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let page = response.body.as_str().unwrap();
+    assert!(page.contains("/api/v1/docs/swagger-ui-bundle.js"));
+    assert!(!page.contains("https://"));
+  }
+}
@@ -1,2 +1,5 @@
+pub mod countries;
+pub mod docs;
+pub mod organizations;
 pub mod status;
 pub mod teachers;
@@ -0,0 +1,87 @@
+use app::services::organizations::create;
+
+use crate::prelude::*;
+use crate::serializers::OrganizationSerializer;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct Params {
+  name: String,
+}
+
+pub async fn handler(
+  request: HttpRequest,
+  db_pool: web::Data<DbPool>,
+  params: web::Json<Params>,
+) -> Result<HttpResponse, ApiError> {
+  let db = db_connect!(db_pool);
+  let access_token: String = require_access_token!(request);
+  let params = params.into_inner();
+
+  let organization = web::block(move || create(params.name, access_token, &db)).await?;
+
+  Ok(http_201!(OrganizationSerializer::from(&organization)))
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::StatusCode, test::TestRequest};
+  use serde_json::json;
+
+  use crate::utils::test::test_app;
+
+  #[actix_rt::test]
+  async fn create_works() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("john.doe@example.com").await;
+
+    let response = app.call_as(
+      &session,
+      TestRequest::post()
+        .uri("/api/v1/organizations")
+        .set_json(&json!({ "name": "SP 1 Warszawa" })),
+    ).await;
+    assert_eq!(response.status, StatusCode::CREATED);
+    assert_eq!(response.body["name"], "SP 1 Warszawa");
+  }
+
+  #[actix_rt::test]
+  async fn create_fails_without_access_token() {
+    let mut app = test_app().await;
+
+    let response = app.call(
+      TestRequest::post()
+        .uri("/api/v1/organizations")
+        .set_json(&json!({ "name": "SP 1 Warszawa" }))
+    ).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+  }
+
+  #[actix_rt::test]
+  async fn create_fails_when_name_is_blank() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("john.doe@example.com").await;
+
+    let response = app.call_as(
+      &session,
+      TestRequest::post().uri("/api/v1/organizations").set_json(&json!({ "name": "" })),
+    ).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.errors(), vec![("name", "blank")]);
+  }
+
+  #[actix_rt::test]
+  async fn create_fails_when_teacher_already_belongs_to_organization() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("john.doe@example.com").await;
+    app.create_organization(&session).await;
+
+    let response = app.call_as(
+      &session,
+      TestRequest::post()
+        .uri("/api/v1/organizations")
+        .set_json(&json!({ "name": "SP 2 Warszawa" })),
+    ).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.errors(), vec![("base", "already_in_organization")]);
+  }
+}
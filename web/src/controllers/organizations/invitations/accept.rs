@@ -0,0 +1,116 @@
+use app::services::organizations::accept_invitation;
+
+use crate::prelude::*;
+use crate::serializers::TeacherSerializer;
+
+pub async fn handler(
+  request: HttpRequest,
+  web::Path(token): web::Path<String>,
+  db_pool: web::Data<DbPool>,
+) -> Result<HttpResponse, ApiError> {
+  let db = db_connect!(db_pool);
+  let access_token: String = require_access_token!(request);
+
+  let teacher = web::block(move || accept_invitation(token, access_token, &db)).await?;
+
+  Ok(http_200!(TeacherSerializer::from(&teacher)))
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::StatusCode, test::TestRequest};
+
+  use db::prelude::*;
+  use crate::prelude::{Duration, Utc};
+  use crate::utils::test::test_app;
+
+  fn accept_uri(invitation: &serde_json::Value) -> String {
+    format!("/api/v1/organizations/invitations/{}/accept", invitation["token"].as_str().unwrap())
+  }
+
+  #[actix_rt::test]
+  async fn accept_works() {
+    let mut app = test_app().await;
+    let admin_session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&admin_session).await;
+    let invitation = app.invite(&admin_session, &organization, "john.doe@example.com").await;
+    let session = app.sign_up_and_in("john.doe@example.com").await;
+
+    let response = app.call_as(&session, TestRequest::post().uri(&accept_uri(&invitation))).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["email"], "john.doe@example.com");
+    assert_eq!(response.body["organization_role"], "member");
+  }
+
+  #[actix_rt::test]
+  async fn accept_fails_without_access_token() {
+    let mut app = test_app().await;
+    let admin_session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&admin_session).await;
+    let invitation = app.invite(&admin_session, &organization, "john.doe@example.com").await;
+
+    let response = app.call(TestRequest::post().uri(&accept_uri(&invitation))).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+  }
+
+  #[actix_rt::test]
+  async fn accept_fails_when_invitation_is_addressed_to_someone_else() {
+    let mut app = test_app().await;
+    let admin_session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&admin_session).await;
+    let invitation = app.invite(&admin_session, &organization, "john.doe@example.com").await;
+    let session = app.sign_up_and_in("jane.doe@example.com").await;
+
+    let response = app.call_as(&session, TestRequest::post().uri(&accept_uri(&invitation))).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+  }
+
+  #[actix_rt::test]
+  async fn accept_fails_when_invitation_doesnt_exist() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("john.doe@example.com").await;
+
+    let response = app.call_as(
+      &session,
+      TestRequest::post().uri("/api/v1/organizations/invitations/token/accept"),
+    ).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+  }
+
+  #[actix_rt::test]
+  async fn accept_fails_when_invitation_is_expired() {
+    let mut app = test_app().await;
+    let admin_session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&admin_session).await;
+    let invitation = app.invite(&admin_session, &organization, "john.doe@example.com").await;
+    let session = app.sign_up_and_in("john.doe@example.com").await;
+    let db = app.db();
+    let invitations_repository = OrganizationInvitationsRepository::new(&db);
+    let mut expired_invitation = invitations_repository
+      .find_by_token(invitation["token"].as_str().unwrap())
+      .unwrap();
+    expired_invitation.expires_at = Utc::now() - Duration::minutes(1);
+    invitations_repository.save(&expired_invitation).unwrap();
+
+    let response = app.call_as(&session, TestRequest::post().uri(&accept_uri(&invitation))).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.errors(), vec![("token", "expired")]);
+  }
+
+  #[actix_rt::test]
+  async fn accept_fails_when_invitation_is_already_accepted() {
+    let mut app = test_app().await;
+    let admin_session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&admin_session).await;
+    let invitation = app.invite(&admin_session, &organization, "john.doe@example.com").await;
+    let session = app.sign_up_and_in("john.doe@example.com").await;
+    app.call_as(&session, TestRequest::post().uri(&accept_uri(&invitation))).await;
+
+    let response = app.call_as(&session, TestRequest::post().uri(&accept_uri(&invitation))).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.errors(),
+      vec![("token", "already_accepted"), ("base", "already_in_organization")],
+    );
+  }
+}
@@ -0,0 +1,112 @@
+use app::services::organizations::invite;
+
+use crate::prelude::*;
+use crate::serializers::OrganizationInvitationSerializer;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct Params {
+  email: String,
+  role: Option<String>,
+}
+
+pub async fn handler(
+  request: HttpRequest,
+  web::Path(organization_uuid): web::Path<String>,
+  db_pool: web::Data<DbPool>,
+  params: web::Json<Params>,
+) -> Result<HttpResponse, ApiError> {
+  let db = db_connect!(db_pool);
+  let access_token: String = require_access_token!(request);
+  let params = params.into_inner();
+
+  let invitation = web::block(move || {
+    invite(organization_uuid, params.email, params.role, access_token, &db)
+  }).await?;
+
+  Ok(http_201!(OrganizationInvitationSerializer::from(&invitation)))
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::StatusCode, test::TestRequest};
+  use serde_json::json;
+
+  use crate::utils::test::test_app;
+
+  fn invitations_uri(organization: &serde_json::Value) -> String {
+    format!("/api/v1/organizations/{}/invitations", organization["uuid"].as_str().unwrap())
+  }
+
+  #[actix_rt::test]
+  async fn create_works() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&session).await;
+    let uri = invitations_uri(&organization);
+
+    let response = app.call_as(
+      &session,
+      TestRequest::post().uri(&uri).set_json(&json!({ "email": "john.doe@example.com" })),
+    ).await;
+    assert_eq!(response.status, StatusCode::CREATED);
+    assert_eq!(response.body["email"], "john.doe@example.com");
+    assert_eq!(response.body["role"], "member");
+  }
+
+  #[actix_rt::test]
+  async fn create_fails_without_access_token() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&session).await;
+    let uri = invitations_uri(&organization);
+
+    let response = app.call(
+      TestRequest::post().uri(&uri).set_json(&json!({ "email": "john.doe@example.com" }))
+    ).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+  }
+
+  #[actix_rt::test]
+  async fn create_fails_when_params_are_invalid() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&session).await;
+    let uri = invitations_uri(&organization);
+
+    let response = app.call_as(
+      &session,
+      TestRequest::post().uri(&uri).set_json(&json!({ "email": "john.doe", "role": "owner" })),
+    ).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.errors(), vec![("email", "invalid"), ("role", "invalid")]);
+  }
+
+  #[actix_rt::test]
+  async fn create_fails_when_teacher_isnt_admin() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&session).await;
+    let member_session = app.add_member(&session, &organization, "member@example.com").await;
+    let uri = invitations_uri(&organization);
+
+    let response = app.call_as(
+      &member_session,
+      TestRequest::post().uri(&uri).set_json(&json!({ "email": "john.doe@example.com" })),
+    ).await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+  }
+
+  #[actix_rt::test]
+  async fn create_fails_when_organization_doesnt_exist() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+
+    let response = app.call_as(
+      &session,
+      TestRequest::post()
+        .uri("/api/v1/organizations/uuid/invitations")
+        .set_json(&json!({ "email": "john.doe@example.com" })),
+    ).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+  }
+}
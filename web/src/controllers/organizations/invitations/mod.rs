@@ -0,0 +1,30 @@
+use crate::prelude::*;
+use crate::serializers::{OrganizationInvitationSerializer, TeacherSerializer};
+
+mod accept;
+mod create;
+
+pub fn config(router: &mut Router) {
+  router
+    // Has to be registered before the organization scoped routes, as "invitations"
+    // would otherwise be matched as an organization UUID
+    .route("/invitations/{token}/accept", Method::POST, accept::handler)
+    .route("/{organization_uuid}/invitations", Method::POST, create::handler);
+}
+
+pub fn openapi(spec: &mut ApiSpec) {
+  spec.operation("POST", "/organizations/invitations/{token}/accept", "Accept an invitation")
+    .path_param("token", "Token sent with the invitation")
+    .bearer_auth()
+    .response::<TeacherSerializer>(200, "The teacher, now a member of the organization")
+    .error(400, "Invitation expired, already accepted or the teacher has an organization")
+    .error(404, "Invitation not found");
+
+  spec.operation("POST", "/organizations/{organization_uuid}/invitations", "Invite a teacher")
+    .path_param("organization_uuid", "UUID of the organization")
+    .bearer_auth()
+    .body::<create::Params>()
+    .response::<OrganizationInvitationSerializer>(201, "Invitation created")
+    .error(403, "Only admins can invite teachers")
+    .error(404, "Organization not found");
+}
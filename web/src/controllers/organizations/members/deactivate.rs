@@ -0,0 +1,96 @@
+use app::services::organizations::deactivate_member;
+
+use crate::prelude::*;
+
+pub async fn handler(
+  request: HttpRequest,
+  web::Path((organization_uuid, teacher_uuid)): web::Path<(String, String)>,
+  db_pool: web::Data<DbPool>,
+) -> Result<HttpResponse, ApiError> {
+  let db = db_connect!(db_pool);
+  let access_token: String = require_access_token!(request);
+
+  web::block(move || deactivate_member(organization_uuid, teacher_uuid, access_token, &db)).await?;
+
+  Ok(http_200!())
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::StatusCode, test::TestRequest};
+
+  use crate::utils::test::test_app;
+
+  fn deactivate_uri(organization: &serde_json::Value, teacher_uuid: &serde_json::Value) -> String {
+    format!(
+      "/api/v1/organizations/{}/members/{}/deactivate",
+      organization["uuid"].as_str().unwrap(),
+      teacher_uuid.as_str().unwrap(),
+    )
+  }
+
+  #[actix_rt::test]
+  async fn deactivate_works() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&session).await;
+    let member_session = app.add_member(&session, &organization, "john.doe@example.com").await;
+    let uri = deactivate_uri(&organization, &member_session["owner_uuid"]);
+
+    let response = app.call_as(&session, TestRequest::patch().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::OK);
+
+    // The member has been signed out
+    let uri = format!("/api/v1/organizations/{}/members", organization["uuid"].as_str().unwrap());
+    let response = app.call_as(&member_session, TestRequest::get().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+  }
+
+  #[actix_rt::test]
+  async fn deactivate_fails_without_access_token() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&session).await;
+    let member_session = app.add_member(&session, &organization, "john.doe@example.com").await;
+    let uri = deactivate_uri(&organization, &member_session["owner_uuid"]);
+
+    let response = app.call(TestRequest::patch().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+  }
+
+  #[actix_rt::test]
+  async fn deactivate_fails_when_admin_deactivates_themselves() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&session).await;
+    let uri = deactivate_uri(&organization, &session["owner_uuid"]);
+
+    let response = app.call_as(&session, TestRequest::patch().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.errors(), vec![("teacher_uuid", "self_deactivation")]);
+  }
+
+  #[actix_rt::test]
+  async fn deactivate_fails_when_teacher_isnt_admin() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&session).await;
+    let member_session = app.add_member(&session, &organization, "john.doe@example.com").await;
+    let uri = deactivate_uri(&organization, &session["owner_uuid"]);
+
+    let response = app.call_as(&member_session, TestRequest::patch().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+  }
+
+  #[actix_rt::test]
+  async fn deactivate_fails_when_teacher_isnt_a_member() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&session).await;
+    let other_session = app.sign_up_and_in("john.doe@example.com").await;
+    let uri = deactivate_uri(&organization, &other_session["owner_uuid"]);
+
+    let response = app.call_as(&session, TestRequest::patch().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+  }
+}
@@ -0,0 +1,72 @@
+use app::services::organizations::list_members;
+
+use crate::prelude::*;
+use crate::serializers::TeacherSerializer;
+
+pub async fn handler(
+  request: HttpRequest,
+  web::Path(organization_uuid): web::Path<String>,
+  db_pool: web::Data<DbPool>,
+) -> Result<HttpResponse, ApiError> {
+  let db = db_connect!(db_pool);
+  let access_token: String = require_access_token!(request);
+
+  let members = web::block(move || list_members(organization_uuid, access_token, &db)).await?;
+
+  Ok(http_200!(members.iter().map(TeacherSerializer::from).collect::<Vec<TeacherSerializer>>()))
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::StatusCode, test::TestRequest};
+
+  use crate::utils::test::test_app;
+
+  #[actix_rt::test]
+  async fn index_works() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&session).await;
+    app.add_member(&session, &organization, "john.doe@example.com").await;
+    let uri = format!("/api/v1/organizations/{}/members", organization["uuid"].as_str().unwrap());
+
+    let response = app.call_as(&session, TestRequest::get().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body.as_array().unwrap().len(), 2);
+  }
+
+  #[actix_rt::test]
+  async fn index_fails_without_access_token() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&session).await;
+    let uri = format!("/api/v1/organizations/{}/members", organization["uuid"].as_str().unwrap());
+
+    let response = app.call(TestRequest::get().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+  }
+
+  #[actix_rt::test]
+  async fn index_fails_when_teacher_isnt_admin() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+    let organization = app.create_organization(&session).await;
+    let member_session = app.add_member(&session, &organization, "john.doe@example.com").await;
+    let uri = format!("/api/v1/organizations/{}/members", organization["uuid"].as_str().unwrap());
+
+    let response = app.call_as(&member_session, TestRequest::get().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::FORBIDDEN);
+  }
+
+  #[actix_rt::test]
+  async fn index_fails_when_organization_doesnt_exist() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("admin@example.com").await;
+
+    let response = app.call_as(
+      &session,
+      TestRequest::get().uri("/api/v1/organizations/uuid/members"),
+    ).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+  }
+}
@@ -0,0 +1,35 @@
+use crate::prelude::*;
+use crate::serializers::TeacherSerializer;
+
+mod deactivate;
+mod index;
+
+pub fn config(router: &mut Router) {
+  router.scope("/{organization_uuid}/members", |router| {
+    router
+      .route("", Method::GET, index::handler)
+      .route("/{teacher_uuid}/deactivate", Method::PATCH, deactivate::handler);
+  });
+}
+
+pub fn openapi(spec: &mut ApiSpec) {
+  spec.operation("GET", "/organizations/{organization_uuid}/members", "List members")
+    .path_param("organization_uuid", "UUID of the organization")
+    .bearer_auth()
+    .response::<Vec<TeacherSerializer>>(200, "Members of the organization")
+    .error(403, "Only admins can list members")
+    .error(404, "Organization not found");
+
+  spec.operation(
+    "PATCH",
+    "/organizations/{organization_uuid}/members/{teacher_uuid}/deactivate",
+    "Deactivate a member",
+  )
+    .path_param("organization_uuid", "UUID of the organization")
+    .path_param("teacher_uuid", "UUID of the member")
+    .bearer_auth()
+    .empty_response(200, "Member deactivated and signed out")
+    .error(400, "Admins can't deactivate themselves")
+    .error(403, "Only admins can deactivate members")
+    .error(404, "Organization or member not found");
+}
@@ -0,0 +1,24 @@
+mod create;
+mod invitations;
+mod members;
+
+use crate::prelude::*;
+use crate::serializers::OrganizationSerializer;
+
+pub fn config(router: &mut Router) {
+  router.scope("/organizations", |router| {
+    router.route("", Method::POST, create::handler);
+    invitations::config(router);
+    members::config(router);
+  });
+}
+
+pub fn openapi(spec: &mut ApiSpec) {
+  spec.operation("POST", "/organizations", "Create an organization")
+    .bearer_auth()
+    .body::<create::Params>()
+    .response::<OrganizationSerializer>(201, "Organization created, with the teacher as its admin");
+
+  invitations::openapi(spec);
+  members::openapi(spec);
+}
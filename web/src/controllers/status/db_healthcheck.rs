@@ -0,0 +1,80 @@
+use std::time::Instant;
+
+use db::prelude::{async_database_pool_status, check_database_connection, database_pool_status};
+
+use crate::prelude::*;
+use crate::serializers::DbStatusSerializer;
+
+// Checks out a connection and round-trips a query, so a pool which can't reach the database
+// is reported as well. The failures are only logged, since the check gets polled.
+pub async fn handler(
+  db_pool: web::Data<DbPool>,
+  db_pool_metrics: web::Data<PoolMetrics>,
+  async_db_pool: web::Data<AsyncDbPool>,
+) -> HttpResponse {
+  let pool = db_pool.get_ref().clone();
+  let started_at = Instant::now();
+
+  let result = web::block(move || {
+    let db = pool.get().map_err(|err| err.to_string())?;
+    check_database_connection(&db).map_err(|err| format!("{:?}", err))
+  }).await;
+
+  let status = DbStatusSerializer::new(
+    result.is_ok(),
+    started_at.elapsed(),
+    &database_pool_status(&db_pool, &db_pool_metrics),
+    &async_database_pool_status(&async_db_pool),
+  );
+
+  match result {
+    Ok(_) => http_200!(status),
+    Err(err) => {
+      error!("The database healthcheck has failed: {}", err);
+      HttpResponse::ServiceUnavailable().json(status)
+    },
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::StatusCode, test::TestRequest};
+
+  use crate::utils::test::{test_app, unreachable_test_app};
+
+  #[actix_rt::test]
+  async fn db_healthcheck_works() {
+    let mut app = test_app().await;
+
+    let response = app.call(TestRequest::get().uri("/api/v1/status/db")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["alive"], true);
+    assert_eq!(response.body["pool"]["max_size"], 2);
+    assert_eq!(response.body["pool"]["in_use"], 0);
+    assert_eq!(response.body["pool"]["checkouts"], 1);
+    assert_eq!(response.body["async_pool"]["max_size"], 2);
+  }
+
+  #[actix_rt::test]
+  async fn db_healthcheck_reports_pool_usage() {
+    let mut app = test_app().await;
+
+    app.sign_up_and_in("john.doe@example.com").await;
+    let response = app.call(TestRequest::get().uri("/api/v1/status/db")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["pool"]["checkouts"], 2);
+    assert_eq!(response.body["async_pool"]["connections"], 1);
+    assert_eq!(response.body["async_pool"]["idle"], 1);
+  }
+
+  #[actix_rt::test]
+  async fn db_healthcheck_fails_when_database_is_unreachable() {
+    let mut app = unreachable_test_app().await;
+
+    let response = app.call(TestRequest::get().uri("/api/v1/status/db")).await;
+    assert_eq!(response.status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.body["alive"], false);
+    assert_eq!(response.body["pool"]["connections"], 0);
+    assert_eq!(response.body["pool"]["timeouts"], 1);
+  }
+}
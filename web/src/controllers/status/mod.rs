@@ -1,13 +1,22 @@
+mod db_healthcheck;
 mod web_healthcheck;
 
 use crate::prelude::*;
+use crate::serializers::DbStatusSerializer;
 
-pub fn config(cfg: &mut web::ServiceConfig) {
-  cfg.service(
-    web::scope("/status")
-      .service(
-        web::resource("/web")
-          .route(web::get().to(web_healthcheck::handler))
-      )
-  );
+pub fn config(router: &mut Router) {
+  router.scope("/status", |router| {
+    router
+      .route("/web", Method::GET, web_healthcheck::handler)
+      .route("/db", Method::GET, db_healthcheck::handler);
+  });
+}
+
+pub fn openapi(spec: &mut ApiSpec) {
+  spec.operation("GET", "/status/web", "Check if the web server is alive")
+    .text_response(200, "The web server is alive");
+
+  spec.operation("GET", "/status/db", "Check if the database is reachable")
+    .response::<DbStatusSerializer>(200, "The database is reachable, with the pools' metrics")
+    .response::<DbStatusSerializer>(503, "The database is unreachable");
 }
@@ -3,3 +3,19 @@ use crate::prelude::*;
 pub async fn handler() -> impl Responder {
   HttpResponse::Ok().body("Web server is alive.")
 }
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::StatusCode, test::TestRequest};
+
+  use crate::utils::test::test_app;
+
+  #[actix_rt::test]
+  async fn web_healthcheck_works() {
+    let mut app = test_app().await;
+
+    let response = app.call(TestRequest::get().uri("/api/v1/status/web")).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body, "Web server is alive.");
+  }
+}
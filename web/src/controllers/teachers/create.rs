@@ -1,24 +1,95 @@
-use app::services::teachers::{sign_up, SignUpError};
+use app::services::teachers::sign_up;
 
 use crate::prelude::*;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct Params {
   email: String,
   password: String,
 }
 
-pub async fn handler(db_pool: web::Data<DbPool>, params: web::Json<Params>) -> impl Responder {
+pub async fn handler(
+  db_pool: web::Data<DbPool>,
+  params: web::Json<Params>,
+) -> Result<HttpResponse, ApiError> {
   let db = db_connect!(db_pool);
   let params = params.into_inner();
 
-  match web::block(move || sign_up(params.email, params.password, &db)).await {
-    Ok(_) => http_201!(),
-    Err(BlockingError::Error(service_errors)) => match service_errors {
-      SignUpError::InvalidParams(errors) => http_400!(ErrorResponse { errors }),
-      SignUpError::UnexpectedError => http_500!(),
-    },
-    Err(BlockingError::Canceled) => http_500!(),
-  }
+  web::block(move || sign_up(params.email, params.password, &db)).await?;
+
+  Ok(http_201!())
 }
 
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::{header, StatusCode}, test::TestRequest};
+  use serde_json::json;
+
+  use crate::utils::test::test_app;
+
+  #[actix_rt::test]
+  async fn create_works() {
+    let mut app = test_app().await;
+
+    let response = app.call(
+      TestRequest::post()
+        .uri("/api/v1/teachers")
+        .set_json(&json!({ "email": "john.doe@example.com", "password": "password" }))
+    ).await;
+    assert_eq!(response.status, StatusCode::CREATED);
+    app.sign_in("john.doe@example.com").await;
+  }
+
+  #[actix_rt::test]
+  async fn create_works_when_email_is_taken() {
+    let mut app = test_app().await;
+    app.sign_up("john.doe@example.com").await;
+
+    let response = app.call(
+      TestRequest::post()
+        .uri("/api/v1/teachers")
+        .set_json(&json!({ "email": "john.doe@example.com", "password": "password" }))
+    ).await;
+    assert_eq!(response.status, StatusCode::CREATED);
+  }
+
+  #[actix_rt::test]
+  async fn create_fails_when_params_are_invalid() {
+    let mut app = test_app().await;
+
+    let response = app.call(
+      TestRequest::post()
+        .uri("/api/v1/teachers")
+        .set_json(&json!({ "email": "", "password": "short" }))
+    ).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.errors(), vec![("email", "blank"), ("password", "too_short")]);
+  }
+
+  #[actix_rt::test]
+  async fn create_fails_when_param_is_missing() {
+    let mut app = test_app().await;
+
+    let response = app.call(
+      TestRequest::post()
+        .uri("/api/v1/teachers")
+        .set_json(&json!({ "email": "john.doe@example.com" }))
+    ).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.errors(), vec![("password", "missing")]);
+  }
+
+  #[actix_rt::test]
+  async fn create_fails_when_body_is_malformed() {
+    let mut app = test_app().await;
+
+    let response = app.call(
+      TestRequest::post()
+        .uri("/api/v1/teachers")
+        .header(header::CONTENT_TYPE, "application/json")
+        .set_payload("{")
+    ).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.errors(), vec![("body", "malformed_json")]);
+  }
+}
@@ -3,10 +3,17 @@ mod create;
 
 use crate::prelude::*;
 
-pub fn config(cfg: &mut web::ServiceConfig) {
-  cfg.service(
-    web::scope("/teachers")
-      .configure(sessions::config)
-      .route("", web::post().to(create::handler))
-  );
+pub fn config(router: &mut Router) {
+  router.scope("/teachers", |router| {
+    sessions::config(router);
+    router.route("", Method::POST, create::handler);
+  });
+}
+
+pub fn openapi(spec: &mut ApiSpec) {
+  spec.operation("POST", "/teachers", "Sign up a teacher")
+    .body::<create::Params>()
+    .empty_response(201, "Teacher signed up");
+
+  sessions::openapi(spec);
 }
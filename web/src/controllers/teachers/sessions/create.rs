@@ -1,24 +1,92 @@
-use app::services::teachers::sessions::{sign_in, SignInError};
+use app::services::teachers::sessions::sign_in_async;
 
 use crate::prelude::*;
 use crate::serializers::SessionSerializer;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct Params {
   email: String,
   password: String,
 }
 
-pub async fn handler(db_pool: web::Data<DbPool>, params: web::Json<Params>) -> impl Responder {
-  let db = db_connect!(db_pool);
+pub async fn handler(
+  settings: web::Data<Settings>,
+  db_pool: web::Data<AsyncDbPool>,
+  params: web::Json<Params>,
+) -> Result<HttpResponse, ApiError> {
+  let mut db = async_db_connect!(db_pool);
   let params = params.into_inner();
 
-  match web::block(move || sign_in(params.email, params.password, &db)).await {
-    Ok(session) => http_201!(SessionSerializer::from(&session)),
-    Err(BlockingError::Error(service_errors)) => match service_errors {
-      SignInError::InvalidParams(errors) => http_400!(ErrorResponse { errors }),
-      SignInError::UnexpectedError => http_500!(),
-    },
-    Err(BlockingError::Canceled) => http_500!(),
+  let session = sign_in_async(params.email, params.password, &settings.sessions, &mut db).await?;
+
+  Ok(http_201!(SessionSerializer::from(&session)))
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::StatusCode, test::TestRequest};
+  use serde_json::json;
+
+  use db::prelude::*;
+  use crate::utils::test::{test_app, PASSWORD};
+
+  #[actix_rt::test]
+  async fn create_works() {
+    let mut app = test_app().await;
+    app.sign_up("john.doe@example.com").await;
+
+    let response = app.call(
+      TestRequest::post()
+        .uri("/api/v1/teachers/sessions")
+        .set_json(&json!({ "email": "john.doe@example.com", "password": PASSWORD }))
+    ).await;
+    assert_eq!(response.status, StatusCode::CREATED);
+    assert!(response.body["access_token"].is_string());
+    assert!(response.body["refresh_token"].is_string());
+  }
+
+  #[actix_rt::test]
+  async fn create_fails_when_params_are_blank() {
+    let mut app = test_app().await;
+
+    let response = app.call(
+      TestRequest::post()
+        .uri("/api/v1/teachers/sessions")
+        .set_json(&json!({ "email": "", "password": "" }))
+    ).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.errors(), vec![("email", "blank"), ("password", "blank")]);
+  }
+
+  #[actix_rt::test]
+  async fn create_fails_when_password_doesnt_match() {
+    let mut app = test_app().await;
+    app.sign_up("john.doe@example.com").await;
+
+    let response = app.call(
+      TestRequest::post()
+        .uri("/api/v1/teachers/sessions")
+        .set_json(&json!({ "email": "john.doe@example.com", "password": "wrong password" }))
+    ).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.errors(), vec![("base", "invalid_credentials")]);
+  }
+
+  #[actix_rt::test]
+  async fn create_fails_when_teacher_is_deactivated() {
+    let mut app = test_app().await;
+    app.sign_up("john.doe@example.com").await;
+    let db = app.db();
+    let teachers_repository = TeachersRepository::new(&db);
+    let teacher = teachers_repository.find_by_email("john.doe@example.com").unwrap();
+    teachers_repository.deactivate(&teacher).unwrap();
+
+    let response = app.call(
+      TestRequest::post()
+        .uri("/api/v1/teachers/sessions")
+        .set_json(&json!({ "email": "john.doe@example.com", "password": PASSWORD }))
+    ).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.errors(), vec![("base", "deactivated")]);
   }
 }
@@ -1,4 +1,4 @@
-use app::services::teachers::sessions::{sign_out, SignOutError};
+use app::services::teachers::sessions::sign_out;
 
 use crate::prelude::*;
 
@@ -6,18 +6,65 @@ pub async fn handler(
   request: HttpRequest,
   web::Path(session_uuid): web::Path<String>,
   db_pool: web::Data<DbPool>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
   let db = db_connect!(db_pool);
   let refresh_token: String = require_refresh_token!(request);
 
-  match web::block(move || sign_out(session_uuid, refresh_token, &db)).await {
-    Ok(_) => http_200!(),
-    Err(BlockingError::Error(service_errors)) => match service_errors {
-      SignOutError::InvalidParams(errors) => http_400!(ErrorResponse { errors }),
-      SignOutError::SessionNotFound => http_404!(),
-      SignOutError::Unauthorized => http_401!(),
-      SignOutError::UnexpectedError => http_500!(),
-    },
-    Err(BlockingError::Canceled) => http_500!(),
+  web::block(move || sign_out(session_uuid, refresh_token, &db)).await?;
+
+  Ok(http_200!())
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::StatusCode, test::TestRequest};
+
+  use crate::utils::test::test_app;
+
+  #[actix_rt::test]
+  async fn destroy_works() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("john.doe@example.com").await;
+    let uri = format!("/api/v1/teachers/sessions/{}", session["uuid"].as_str().unwrap());
+    let refresh_token = session["refresh_token"].as_str().unwrap();
+
+    let response = app.call_with_token(refresh_token, TestRequest::delete().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::OK);
+
+    let response = app.call_with_token(refresh_token, TestRequest::delete().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+  }
+
+  #[actix_rt::test]
+  async fn destroy_fails_without_refresh_token() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("john.doe@example.com").await;
+    let uri = format!("/api/v1/teachers/sessions/{}", session["uuid"].as_str().unwrap());
+
+    let response = app.call(TestRequest::delete().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(response.errors(), vec![("base", "unauthorized")]);
+  }
+
+  #[actix_rt::test]
+  async fn destroy_fails_when_refresh_token_is_invalid() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("john.doe@example.com").await;
+    let uri = format!("/api/v1/teachers/sessions/{}", session["uuid"].as_str().unwrap());
+
+    // The access token doesn't grant access to the session
+    let response = app.call_as(&session, TestRequest::delete().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+  }
+
+  #[actix_rt::test]
+  async fn destroy_fails_when_session_doesnt_exist() {
+    let mut app = test_app().await;
+
+    let response = app.call_with_token(
+      "refresh_token",
+      TestRequest::delete().uri("/api/v1/teachers/sessions/uuid"),
+    ).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
   }
 }
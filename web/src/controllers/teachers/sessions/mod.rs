@@ -1,17 +1,36 @@
 use crate::prelude::*;
+use crate::serializers::SessionSerializer;
 
 mod create;
 mod destroy;
 mod refresh;
 
-pub fn config(cfg: &mut web::ServiceConfig) {
-  cfg.service(
-    web::scope("/sessions")
-      .route("", web::post().to(create::handler))
-      .service(
-        web::scope("/{session_uuid}")
-          .route("", web::delete().to(destroy::handler))
-          .route("/refresh", web::patch().to(refresh::handler))
-      )
-  );
+pub fn config(router: &mut Router) {
+  router.scope("/sessions", |router| {
+    router
+      .route("", Method::POST, create::handler)
+      .scope("/{session_uuid}", |router| {
+        router
+          .route("", Method::DELETE, destroy::handler)
+          .route("/refresh", Method::PATCH, refresh::handler);
+      });
+  });
+}
+
+pub fn openapi(spec: &mut ApiSpec) {
+  spec.operation("POST", "/teachers/sessions", "Sign in a teacher")
+    .body::<create::Params>()
+    .response::<SessionSerializer>(201, "Session created");
+
+  spec.operation("DELETE", "/teachers/sessions/{session_uuid}", "Sign out a teacher")
+    .path_param("session_uuid", "UUID of the session")
+    .bearer_auth()
+    .empty_response(200, "Session destroyed")
+    .error(404, "Session not found");
+
+  spec.operation("PATCH", "/teachers/sessions/{session_uuid}/refresh", "Refresh a session")
+    .path_param("session_uuid", "UUID of the session")
+    .bearer_auth()
+    .response::<SessionSerializer>(200, "Session with new tokens")
+    .error(404, "Session not found");
 }
@@ -1,4 +1,4 @@
-use app::services::teachers::sessions::{refresh, RefreshError};
+use app::services::teachers::sessions::refresh;
 
 use crate::prelude::*;
 use crate::serializers::SessionSerializer;
@@ -6,19 +6,81 @@ use crate::serializers::SessionSerializer;
 pub async fn handler(
   request: HttpRequest,
   web::Path(session_uuid): web::Path<String>,
+  settings: web::Data<Settings>,
   db: web::Data<DbPool>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
   let conn = db_connect!(db);
   let refresh_token: String = require_refresh_token!(request);
+  let lifetimes = settings.sessions;
 
-  match web::block(move || refresh(session_uuid, refresh_token, &conn)).await {
-    Ok(session) => http_200!(SessionSerializer::from(&session)),
-    Err(BlockingError::Error(service_errors)) => match service_errors {
-      RefreshError::InvalidParams(errors) => http_400!(ErrorResponse { errors }),
-      RefreshError::SessionNotFound => http_404!(),
-      RefreshError::Unauthorized => http_401!(),
-      RefreshError::UnexpectedError => http_500!(),
-    },
-    Err(BlockingError::Canceled) => http_500!(),
+  let session = web::block(move || refresh(session_uuid, refresh_token, &lifetimes, &conn)).await?;
+
+  Ok(http_200!(SessionSerializer::from(&session)))
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::{header, StatusCode}, test::TestRequest};
+
+  use crate::utils::test::test_app;
+
+  #[actix_rt::test]
+  async fn refresh_works() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("john.doe@example.com").await;
+    let uri = format!("/api/v1/teachers/sessions/{}/refresh", session["uuid"].as_str().unwrap());
+
+    let response = app.call_with_token(
+      session["refresh_token"].as_str().unwrap(),
+      TestRequest::patch().uri(&uri),
+    ).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["uuid"], session["uuid"]);
+    assert_ne!(response.body["access_token"], session["access_token"]);
+  }
+
+  #[actix_rt::test]
+  async fn refresh_fails_without_refresh_token() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("john.doe@example.com").await;
+    let uri = format!("/api/v1/teachers/sessions/{}/refresh", session["uuid"].as_str().unwrap());
+
+    let response = app.call(TestRequest::patch().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+
+    let response = app.call(
+      TestRequest::patch().uri(&uri).header(header::AUTHORIZATION, "Bearer ")
+    ).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+
+    let response = app.call(
+      TestRequest::patch()
+        .uri(&uri)
+        .header(header::AUTHORIZATION, session["refresh_token"].as_str().unwrap())
+    ).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+  }
+
+  #[actix_rt::test]
+  async fn refresh_fails_when_refresh_token_is_invalid() {
+    let mut app = test_app().await;
+    let session = app.sign_up_and_in("john.doe@example.com").await;
+    let uri = format!("/api/v1/teachers/sessions/{}/refresh", session["uuid"].as_str().unwrap());
+
+    let response = app.call_with_token("refresh_token", TestRequest::patch().uri(&uri)).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(response.errors(), vec![("base", "unauthorized")]);
+  }
+
+  #[actix_rt::test]
+  async fn refresh_fails_when_session_doesnt_exist() {
+    let mut app = test_app().await;
+
+    let response = app.call_with_token(
+      "refresh_token",
+      TestRequest::patch().uri("/api/v1/teachers/sessions/uuid/refresh"),
+    ).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+    assert_eq!(response.errors(), vec![("base", "not_found")]);
   }
 }
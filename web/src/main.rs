@@ -4,24 +4,26 @@ mod serializers;
 mod utils;
 mod prelude;
 
-use actix_web::{middleware, App, HttpServer};
+use actix_web::HttpServer;
 
 use config::initializers;
-use config::routes;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
   println!("* Running initializers..");
 
   match initializers::run() {
-    Ok((port, db_connection_pool)) => {
+    Ok((settings, db_connection_pool, db_connection_pool_metrics, async_db_connection_pool)) => {
       println!("* Spinning up the application server..");
 
+      let port = settings.port;
       let server = HttpServer::new(move || {
-        App::new()
-          .data(db_connection_pool.clone())
-          .configure(routes::config)
-          .wrap(middleware::Logger::default())
+        build_app!(
+          settings.clone(),
+          db_connection_pool.clone(),
+          db_connection_pool_metrics.clone(),
+          async_db_connection_pool.clone()
+        )
       }).bind(format!("0.0.0.0:{}", port))?;
 
       println!("* The application is running at 0.0.0.0:{}", port);
@@ -29,8 +31,8 @@ async fn main() -> std::io::Result<()> {
       server.run().await
     },
     Err(error) => {
-      println!("{}", error);
-      return Ok(());
+      eprintln!("{}", error);
+      std::process::exit(1);
     }
   }
 }
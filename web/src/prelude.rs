@@ -1,24 +1,28 @@
 pub use chrono::{DateTime, Duration, Utc};
 pub use actix_web::{
+  http::Method,
   web,
-  rt::blocking::BlockingError,
   HttpRequest,
   HttpResponse,
   Responder,
 };
 pub use serde::{Serialize, Deserialize};
+pub use schemars::JsonSchema;
 pub use log::error;
+// Used by `report_unexpected_err!` expanded in `db_connect!`.
+#[cfg(not(test))]
 pub use app::prelude::ROLLBAR_CLIENT;
-pub use db::prelude::DbPool;
+pub use db::prelude::{AsyncDbPool, DbPool, PoolMetrics};
 
 pub use crate::{
+  async_db_connect,
   db_connect,
+  require_access_token,
   require_refresh_token,
   http_200,
   http_201,
-  http_400,
-  http_401,
-  http_404,
-  http_500,
-  utils::responses::{ErrorResponse, EmptyResponse}
+  config::settings::Settings,
+  utils::errors::ApiError,
+  utils::openapi::ApiSpec,
+  utils::router::Router
 };
@@ -0,0 +1,30 @@
+use db::models::Country;
+
+use crate::prelude::*;
+
+#[derive(Serialize, JsonSchema)]
+pub struct CountrySerializer<'a> {
+  alpha2_code: &'a str,
+  alpha3_code: &'a str,
+  name_pl: &'a str,
+  name_en: &'a str,
+  capital_pl: &'a str,
+  capital_en: &'a str,
+  continent: &'a str,
+  neighbours: &'a [String],
+}
+
+impl<'a> From<&'a Country> for CountrySerializer<'a> {
+  fn from(country: &'a Country) -> Self {
+    CountrySerializer {
+      alpha2_code: &country.alpha2_code,
+      alpha3_code: &country.alpha3_code,
+      name_pl: &country.name_pl,
+      name_en: &country.name_en,
+      capital_pl: &country.capital_pl,
+      capital_en: &country.capital_en,
+      continent: &country.continent,
+      neighbours: &country.neighbours,
+    }
+  }
+}
@@ -0,0 +1,81 @@
+use std::time;
+
+use db::utils::connection_pool::{AsyncPoolStatus, PoolStatus};
+
+use crate::prelude::*;
+
+#[derive(Serialize, JsonSchema)]
+pub struct DbStatusSerializer {
+  alive: bool,
+  round_trip_ms: f64,
+  pool: PoolStatusSerializer,
+  async_pool: AsyncPoolStatusSerializer,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct PoolStatusSerializer {
+  max_size: u32,
+  connections: u32,
+  idle: u32,
+  in_use: u32,
+  checkouts: u64,
+  timeouts: u64,
+  average_wait_ms: f64,
+  max_wait_ms: f64,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct AsyncPoolStatusSerializer {
+  max_size: u32,
+  connections: u32,
+  idle: u32,
+  in_use: u32,
+  waiting: u32,
+}
+
+fn milliseconds(duration: time::Duration) -> f64 {
+  duration.as_secs_f64() * 1000.0
+}
+
+impl DbStatusSerializer {
+  pub fn new(
+    alive: bool,
+    round_trip: time::Duration,
+    pool: &PoolStatus,
+    async_pool: &AsyncPoolStatus,
+  ) -> Self {
+    DbStatusSerializer {
+      alive,
+      round_trip_ms: milliseconds(round_trip),
+      pool: PoolStatusSerializer::from(pool),
+      async_pool: AsyncPoolStatusSerializer::from(async_pool),
+    }
+  }
+}
+
+impl From<&PoolStatus> for PoolStatusSerializer {
+  fn from(status: &PoolStatus) -> Self {
+    PoolStatusSerializer {
+      max_size: status.max_size,
+      connections: status.connections,
+      idle: status.idle,
+      in_use: status.in_use,
+      checkouts: status.checkouts,
+      timeouts: status.timeouts,
+      average_wait_ms: milliseconds(status.average_wait_time),
+      max_wait_ms: milliseconds(status.max_wait_time),
+    }
+  }
+}
+
+impl From<&AsyncPoolStatus> for AsyncPoolStatusSerializer {
+  fn from(status: &AsyncPoolStatus) -> Self {
+    AsyncPoolStatusSerializer {
+      max_size: status.max_size,
+      connections: status.connections,
+      idle: status.idle,
+      in_use: status.in_use,
+      waiting: status.waiting,
+    }
+  }
+}
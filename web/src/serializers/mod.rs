@@ -1,3 +1,13 @@
+mod country_serializer;
+mod db_status_serializer;
+mod organization_invitation_serializer;
+mod organization_serializer;
 mod session_serializer;
+mod teacher_serializer;
 
+pub use country_serializer::CountrySerializer;
+pub use db_status_serializer::DbStatusSerializer;
+pub use organization_invitation_serializer::OrganizationInvitationSerializer;
+pub use organization_serializer::OrganizationSerializer;
 pub use session_serializer::SessionSerializer;
+pub use teacher_serializer::TeacherSerializer;
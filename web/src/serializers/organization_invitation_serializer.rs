@@ -0,0 +1,26 @@
+use db::models::OrganizationInvitation;
+
+use crate::prelude::*;
+
+#[derive(Serialize, JsonSchema)]
+pub struct OrganizationInvitationSerializer<'a> {
+  uuid: &'a str,
+  email: &'a str,
+  role: &'a str,
+  token: &'a str,
+  expires_at: &'a DateTime<Utc>,
+  accepted_at: Option<&'a DateTime<Utc>>,
+}
+
+impl<'a> From<&'a OrganizationInvitation> for OrganizationInvitationSerializer<'a> {
+  fn from(invitation: &'a OrganizationInvitation) -> Self {
+    OrganizationInvitationSerializer {
+      uuid: &invitation.uuid,
+      email: &invitation.email,
+      role: &invitation.role,
+      token: &invitation.token,
+      expires_at: &invitation.expires_at,
+      accepted_at: invitation.accepted_at.as_ref(),
+    }
+  }
+}
@@ -0,0 +1,18 @@
+use db::models::Organization;
+
+use crate::prelude::*;
+
+#[derive(Serialize, JsonSchema)]
+pub struct OrganizationSerializer<'a> {
+  uuid: &'a str,
+  name: &'a str,
+}
+
+impl<'a> From<&'a Organization> for OrganizationSerializer<'a> {
+  fn from(organization: &'a Organization) -> Self {
+    OrganizationSerializer {
+      uuid: &organization.uuid,
+      name: &organization.name,
+    }
+  }
+}
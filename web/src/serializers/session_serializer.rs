@@ -2,7 +2,7 @@ use db::models::Session;
 
 use crate::prelude::*;
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct SessionSerializer<'a> {
   uuid: &'a str,
   owner_uuid: &'a str,
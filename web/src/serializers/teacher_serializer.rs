@@ -0,0 +1,22 @@
+use db::models::Teacher;
+
+use crate::prelude::*;
+
+#[derive(Serialize, JsonSchema)]
+pub struct TeacherSerializer<'a> {
+  uuid: &'a str,
+  email: &'a str,
+  organization_role: Option<&'a str>,
+  deactivated_at: Option<&'a DateTime<Utc>>,
+}
+
+impl<'a> From<&'a Teacher> for TeacherSerializer<'a> {
+  fn from(teacher: &'a Teacher) -> Self {
+    TeacherSerializer {
+      uuid: &teacher.uuid,
+      email: &teacher.email,
+      organization_role: teacher.organization_role.as_deref(),
+      deactivated_at: teacher.deactivated_at.as_ref(),
+    }
+  }
+}
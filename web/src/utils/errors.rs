@@ -0,0 +1,413 @@
+use std::collections::HashMap;
+
+use actix_web::{
+  dev::ServiceResponse,
+  error::JsonPayloadError,
+  http::{header, StatusCode},
+  rt::blocking::BlockingError,
+  HttpRequest,
+  HttpResponse,
+  ResponseError,
+};
+use thiserror::Error;
+
+use app::services::countries::{FindError, ListError};
+use app::services::organizations::{
+  AcceptInvitationError,
+  CreateError,
+  DeactivateMemberError,
+  InviteError,
+  ListMembersError,
+};
+use app::services::teachers::SignUpError;
+use app::services::teachers::sessions::{RefreshError, SignInError, SignOutError};
+
+use app::utils::errors::FieldError;
+
+use crate::utils::locale::Locale;
+use crate::utils::responses::{ErrorResponse, FieldErrorResponse};
+
+#[derive(PartialEq, Error, Debug)]
+pub enum ApiError {
+  #[error("Invalid params: {0:?}")]
+  InvalidParams(Vec<FieldErrorResponse>),
+  #[error("Unauthorized")]
+  Unauthorized,
+  #[error("Forbidden")]
+  Forbidden,
+  #[error("Not found")]
+  NotFound,
+  #[error("Unexpected error has occurred")]
+  UnexpectedError,
+}
+
+impl ApiError {
+  // Machine-readable counterpart of the message, these must stay stable as clients rely on them
+  pub fn code(&self) -> &'static str {
+    match self {
+      ApiError::InvalidParams(_) => "invalid_params",
+      ApiError::Unauthorized => "unauthorized",
+      ApiError::Forbidden => "forbidden",
+      ApiError::NotFound => "not_found",
+      ApiError::UnexpectedError => "unexpected_error",
+    }
+  }
+
+  pub fn invalid_params<E: FieldError>(errors: Vec<E>) -> Self {
+    ApiError::InvalidParams(errors.iter().map(|error| FieldErrorResponse {
+      field: error.field().to_string(),
+      code: error.code().to_string(),
+      message: error.to_string(),
+    }).collect())
+  }
+
+  pub fn invalid_param(field: &str, code: &str, message: String) -> Self {
+    ApiError::InvalidParams(vec![FieldErrorResponse {
+      field: field.to_string(),
+      code: code.to_string(),
+      message,
+    }])
+  }
+}
+
+impl ResponseError for ApiError {
+  fn status_code(&self) -> StatusCode {
+    match self {
+      ApiError::InvalidParams(_) => StatusCode::BAD_REQUEST,
+      ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+      ApiError::Forbidden => StatusCode::FORBIDDEN,
+      ApiError::NotFound => StatusCode::NOT_FOUND,
+      ApiError::UnexpectedError => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+  }
+
+  fn error_response(&self) -> HttpResponse {
+    self.localized_response(Locale::default())
+  }
+}
+
+impl ApiError {
+  pub fn localized_response(&self, locale: Locale) -> HttpResponse {
+    let errors = match self {
+      ApiError::InvalidParams(errors) => errors.clone(),
+      // Errors that aren't caused by a specific param are reported against the "base" field
+      error => vec![FieldErrorResponse {
+        field: "base".to_string(),
+        code: self.code().to_string(),
+        message: error.to_string(),
+      }],
+    };
+
+    HttpResponse::build(self.status_code())
+      .header(header::CONTENT_LANGUAGE, locale.code())
+      .json(ErrorResponse {
+        code: self.code(),
+        errors: errors.into_iter().map(|error| localize(error, locale)).collect(),
+      })
+  }
+}
+
+// Messages are looked up by the field and the code first (e.g. "email-blank"), then by the code
+// alone for the generic ones (e.g. "missing"). The message from the service is the last resort.
+fn localize(error: FieldErrorResponse, locale: Locale) -> FieldErrorResponse {
+  let message = translate(&error.field, &error.code, locale).unwrap_or(error.message);
+
+  FieldErrorResponse { message, ..error }
+}
+
+fn translate(field: &str, code: &str, locale: Locale) -> Option<String> {
+  locale.translate(&format!("{}-{}", field, code))
+    .or_else(|| {
+      let mut args = HashMap::new();
+      args.insert("field", field.into());
+      locale.translate_with_args(code, &args)
+    })
+}
+
+// Errors are rendered in the default language by `ResponseError`, which has no access to the
+// request, so this re-renders them in the language picked by the client via `Accept-Language`
+pub fn localize_error_response(response: ServiceResponse, locale: Locale) -> ServiceResponse {
+  let localized_response = response.response().error()
+    .and_then(|error| error.as_error::<ApiError>())
+    .map(|error| error.localized_response(locale));
+
+  match localized_response {
+    Some(localized_response) => response.into_response(localized_response),
+    None => response,
+  }
+}
+
+impl<E> From<BlockingError<E>> for ApiError where E: Into<ApiError> + std::fmt::Debug {
+  fn from(error: BlockingError<E>) -> Self {
+    match error {
+      BlockingError::Error(error) => error.into(),
+      BlockingError::Canceled => ApiError::UnexpectedError,
+    }
+  }
+}
+
+// Used as the `web::JsonConfig` error handler, so that malformed request bodies are reported
+// in the same shape as the validation errors instead of actix's default plaintext response
+pub fn json_error_handler(error: JsonPayloadError, _request: &HttpRequest) -> actix_web::Error {
+  let api_error = match error {
+    JsonPayloadError::Deserialize(error) if error.is_data() => {
+      let message = error.to_string();
+
+      // serde doesn't expose the name of the missing field other than through the message,
+      // its format is pinned by `json_error_handler_reports_missing_field`
+      match message.strip_prefix("missing field `").and_then(|rest| rest.split('`').next()) {
+        Some(field) => ApiError::invalid_param(field, "missing", format!("{} is missing", field)),
+        None => ApiError::invalid_param("body", "invalid_type", message),
+      }
+    },
+    JsonPayloadError::Deserialize(_) => {
+      ApiError::invalid_param("body", "malformed_json", "Request body is not valid JSON".into())
+    },
+    JsonPayloadError::ContentType => {
+      ApiError::invalid_param("body", "invalid_content_type", "Content type must be JSON".into())
+    },
+    JsonPayloadError::Overflow => {
+      ApiError::invalid_param("body", "too_large", "Request body is too large".into())
+    },
+    JsonPayloadError::Payload(_) => {
+      ApiError::invalid_param("body", "unreadable", "Request body couldn't be read".into())
+    },
+  };
+
+  api_error.into()
+}
+
+impl From<SignUpError> for ApiError {
+  fn from(error: SignUpError) -> Self {
+    match error {
+      SignUpError::InvalidParams(errors) => ApiError::invalid_params(errors),
+      SignUpError::UnexpectedError => ApiError::UnexpectedError,
+    }
+  }
+}
+
+impl From<SignInError> for ApiError {
+  fn from(error: SignInError) -> Self {
+    match error {
+      SignInError::InvalidParams(errors) => ApiError::invalid_params(errors),
+      SignInError::UnexpectedError => ApiError::UnexpectedError,
+    }
+  }
+}
+
+impl From<RefreshError> for ApiError {
+  fn from(error: RefreshError) -> Self {
+    match error {
+      RefreshError::InvalidParams(errors) => ApiError::invalid_params(errors),
+      RefreshError::SessionNotFound => ApiError::NotFound,
+      RefreshError::Unauthorized => ApiError::Unauthorized,
+      RefreshError::UnexpectedError => ApiError::UnexpectedError,
+    }
+  }
+}
+
+impl From<SignOutError> for ApiError {
+  fn from(error: SignOutError) -> Self {
+    match error {
+      SignOutError::InvalidParams(errors) => ApiError::invalid_params(errors),
+      SignOutError::SessionNotFound => ApiError::NotFound,
+      SignOutError::Unauthorized => ApiError::Unauthorized,
+      SignOutError::UnexpectedError => ApiError::UnexpectedError,
+    }
+  }
+}
+
+impl From<ListError> for ApiError {
+  fn from(error: ListError) -> Self {
+    match error {
+      ListError::InvalidParams(errors) => ApiError::invalid_params(errors),
+      ListError::UnexpectedError => ApiError::UnexpectedError,
+    }
+  }
+}
+
+impl From<FindError> for ApiError {
+  fn from(error: FindError) -> Self {
+    match error {
+      FindError::InvalidParams(errors) => ApiError::invalid_params(errors),
+      FindError::CountryNotFound => ApiError::NotFound,
+      FindError::UnexpectedError => ApiError::UnexpectedError,
+    }
+  }
+}
+
+impl From<CreateError> for ApiError {
+  fn from(error: CreateError) -> Self {
+    match error {
+      CreateError::InvalidParams(errors) => ApiError::invalid_params(errors),
+      CreateError::Unauthorized => ApiError::Unauthorized,
+      CreateError::UnexpectedError => ApiError::UnexpectedError,
+    }
+  }
+}
+
+impl From<InviteError> for ApiError {
+  fn from(error: InviteError) -> Self {
+    match error {
+      InviteError::InvalidParams(errors) => ApiError::invalid_params(errors),
+      InviteError::OrganizationNotFound => ApiError::NotFound,
+      InviteError::Unauthorized => ApiError::Unauthorized,
+      InviteError::Forbidden => ApiError::Forbidden,
+      InviteError::UnexpectedError => ApiError::UnexpectedError,
+    }
+  }
+}
+
+impl From<AcceptInvitationError> for ApiError {
+  fn from(error: AcceptInvitationError) -> Self {
+    match error {
+      AcceptInvitationError::InvalidParams(errors) => ApiError::invalid_params(errors),
+      AcceptInvitationError::InvitationNotFound => ApiError::NotFound,
+      AcceptInvitationError::Unauthorized => ApiError::Unauthorized,
+      AcceptInvitationError::UnexpectedError => ApiError::UnexpectedError,
+    }
+  }
+}
+
+impl From<ListMembersError> for ApiError {
+  fn from(error: ListMembersError) -> Self {
+    match error {
+      ListMembersError::InvalidParams(errors) => ApiError::invalid_params(errors),
+      ListMembersError::OrganizationNotFound => ApiError::NotFound,
+      ListMembersError::Unauthorized => ApiError::Unauthorized,
+      ListMembersError::Forbidden => ApiError::Forbidden,
+      ListMembersError::UnexpectedError => ApiError::UnexpectedError,
+    }
+  }
+}
+
+impl From<DeactivateMemberError> for ApiError {
+  fn from(error: DeactivateMemberError) -> Self {
+    match error {
+      DeactivateMemberError::InvalidParams(errors) => ApiError::invalid_params(errors),
+      DeactivateMemberError::OrganizationNotFound => ApiError::NotFound,
+      DeactivateMemberError::TeacherNotFound => ApiError::NotFound,
+      DeactivateMemberError::Unauthorized => ApiError::Unauthorized,
+      DeactivateMemberError::Forbidden => ApiError::Forbidden,
+      DeactivateMemberError::UnexpectedError => ApiError::UnexpectedError,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::{http::{header, StatusCode}, test::TestRequest};
+  use serde::Deserialize;
+  use serde_json::json;
+
+  use app::services::countries::{FindValidationError, ListValidationError};
+  use app::services::organizations::{
+    AcceptInvitationValidationError,
+    CreateValidationError,
+    DeactivateMemberValidationError,
+    InviteValidationError,
+    ListMembersValidationError,
+  };
+  use app::services::teachers::SignUpValidationError;
+  use app::services::teachers::sessions::{
+    RefreshValidationError,
+    SignInValidationError,
+    SignOutValidationError,
+  };
+
+  use super::*;
+  use crate::utils::test::test_app;
+
+  const LOCALES: [Locale; 2] = [Locale::Pl, Locale::En];
+
+  fn untranslated_errors<E: FieldError>() -> Vec<(&'static str, &'static str, Locale)> {
+    E::variants().iter()
+      .flat_map(|error| LOCALES.iter().map(move |locale| (error.field(), error.code(), *locale)))
+      .filter(|(field, code, locale)| translate(field, code, *locale).is_none())
+      .collect()
+  }
+
+  #[test]
+  fn every_field_error_is_translated() {
+    let untranslated = [
+      untranslated_errors::<SignUpValidationError>(),
+      untranslated_errors::<SignInValidationError>(),
+      untranslated_errors::<RefreshValidationError>(),
+      untranslated_errors::<SignOutValidationError>(),
+      untranslated_errors::<ListValidationError>(),
+      untranslated_errors::<FindValidationError>(),
+      untranslated_errors::<CreateValidationError>(),
+      untranslated_errors::<InviteValidationError>(),
+      untranslated_errors::<AcceptInvitationValidationError>(),
+      untranslated_errors::<ListMembersValidationError>(),
+      untranslated_errors::<DeactivateMemberValidationError>(),
+    ].concat();
+
+    assert_eq!(untranslated, vec![]);
+  }
+
+  #[test]
+  fn every_api_error_is_translated() {
+    // The invalid params are rendered with the messages of their field errors instead
+    let errors = [
+      ApiError::Unauthorized,
+      ApiError::Forbidden,
+      ApiError::NotFound,
+      ApiError::UnexpectedError,
+    ];
+    let untranslated: Vec<_> = errors.iter()
+      .flat_map(|error| LOCALES.iter().map(move |locale| (error.code(), *locale)))
+      .filter(|(code, locale)| translate("base", code, *locale).is_none())
+      .collect();
+
+    assert_eq!(untranslated, vec![]);
+  }
+
+  #[derive(Deserialize, Debug)]
+  struct Params {
+    #[allow(dead_code)]
+    email: String,
+  }
+
+  #[test]
+  fn json_error_handler_reports_missing_field() {
+    let error = serde_json::from_slice::<Params>(b"{}").unwrap_err();
+    assert_eq!(error.to_string(), "missing field `email` at line 1 column 2");
+
+    let request = TestRequest::default().to_http_request();
+    let error = json_error_handler(JsonPayloadError::Deserialize(error), &request);
+    assert_eq!(
+      error.as_error::<ApiError>(),
+      Some(&ApiError::invalid_param("email", "missing", "email is missing".into())),
+    );
+  }
+
+  #[actix_rt::test]
+  async fn errors_are_in_polish_by_default() {
+    let mut app = test_app().await;
+
+    let response = app.call(
+      TestRequest::post()
+        .uri("/api/v1/teachers")
+        .set_json(&json!({ "email": "", "password": "password" }))
+    ).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers.get(header::CONTENT_LANGUAGE).unwrap(), "pl");
+    assert_eq!(response.body["errors"][0]["message"], "Adres e-mail nie może być pusty");
+  }
+
+  #[actix_rt::test]
+  async fn errors_are_in_the_accepted_language() {
+    let mut app = test_app().await;
+
+    let response = app.call(
+      TestRequest::post()
+        .uri("/api/v1/teachers")
+        .header(header::ACCEPT_LANGUAGE, "en")
+        .set_json(&json!({ "email": "", "password": "password" }))
+    ).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers.get(header::CONTENT_LANGUAGE).unwrap(), "en");
+    assert_eq!(response.body["errors"][0]["message"], "Email can't be blank");
+  }
+}
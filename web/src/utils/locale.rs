@@ -0,0 +1,137 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use actix_web::http::{header, HeaderMap};
+use fluent_templates::{static_loader, LanguageIdentifier};
+use fluent_templates::fluent_bundle::FluentValue;
+use fluent_templates::loader::langid;
+
+static_loader! {
+  static LOCALES = {
+    locales: "./locales",
+    fallback_language: "pl",
+    // Don't wrap the arguments in unicode isolation marks, the messages end up in plain JSON
+    customise: |bundle| bundle.set_use_isolating(false),
+  };
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Locale {
+  // Most of the users are Polish teachers and students
+  #[default]
+  Pl,
+  En,
+}
+
+impl Locale {
+  pub fn from_headers(headers: &HeaderMap) -> Self {
+    headers.get(header::ACCEPT_LANGUAGE)
+      .and_then(|value| value.to_str().ok())
+      .and_then(Self::from_accept_language)
+      .unwrap_or_default()
+  }
+
+  // Picks the supported language with the highest weight, e.g. "de, en-GB;q=0.8, pl;q=0.5" => En
+  pub fn from_accept_language(value: &str) -> Option<Self> {
+    let mut languages: Vec<(Self, f32)> = value.split(',')
+      .filter_map(|entry| {
+        let mut parts = entry.split(';').map(str::trim);
+        let locale = match parts.next()?.split('-').next()?.to_lowercase().as_str() {
+          "pl" => Locale::Pl,
+          "en" => Locale::En,
+          _ => return None,
+        };
+        let weight = parts
+          .find_map(|part| part.strip_prefix("q="))
+          .and_then(|weight| weight.parse().ok())
+          .unwrap_or(1.0);
+
+        Some((locale, weight))
+      })
+      .filter(|(_, weight)| *weight > 0.0)
+      .collect();
+
+    // The sort is stable, so languages with equal weights keep the order from the header
+    languages.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+    languages.first().map(|(locale, _)| *locale)
+  }
+
+  pub fn code(&self) -> &'static str {
+    match self {
+      Locale::Pl => "pl",
+      Locale::En => "en",
+    }
+  }
+
+  pub fn translate(&self, key: &str) -> Option<String> {
+    LOCALES.lookup_single_language::<&str>(&self.language(), key, None)
+  }
+
+  pub fn translate_with_args(&self, key: &str, args: &HashMap<&str, FluentValue>) -> Option<String> {
+    LOCALES.lookup_single_language(&self.language(), key, Some(args))
+  }
+
+  fn language(&self) -> LanguageIdentifier {
+    match self {
+      Locale::Pl => langid!("pl"),
+      Locale::En => langid!("en"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use actix_web::test::TestRequest;
+
+  use super::*;
+
+  #[test]
+  fn from_accept_language_works() {
+    assert_eq!(Locale::from_accept_language("en"), Some(Locale::En));
+    assert_eq!(Locale::from_accept_language("pl-PL"), Some(Locale::Pl));
+    assert_eq!(Locale::from_accept_language("EN-gb"), Some(Locale::En));
+  }
+
+  #[test]
+  fn from_accept_language_picks_the_highest_weight() {
+    assert_eq!(Locale::from_accept_language("pl;q=0.5, en;q=0.8"), Some(Locale::En));
+    assert_eq!(Locale::from_accept_language("en;q=0.9, pl"), Some(Locale::Pl));
+    assert_eq!(Locale::from_accept_language("en, pl"), Some(Locale::En));
+  }
+
+  #[test]
+  fn from_accept_language_skips_unsupported_languages() {
+    assert_eq!(Locale::from_accept_language("de, en-GB;q=0.8, pl;q=0.5"), Some(Locale::En));
+    assert_eq!(Locale::from_accept_language("de, fr;q=0.8"), None);
+    assert_eq!(Locale::from_accept_language("*"), None);
+  }
+
+  #[test]
+  fn from_accept_language_skips_rejected_languages() {
+    assert_eq!(Locale::from_accept_language("pl;q=0, en;q=0.1"), Some(Locale::En));
+    assert_eq!(Locale::from_accept_language("en;q=0"), None);
+  }
+
+  #[test]
+  fn from_accept_language_ignores_invalid_weights() {
+    assert_eq!(Locale::from_accept_language("en;q=abc, pl;q=0.5"), Some(Locale::En));
+  }
+
+  #[test]
+  fn from_headers_falls_back_to_polish() {
+    let request = TestRequest::default().to_http_request();
+    assert_eq!(Locale::from_headers(request.headers()), Locale::Pl);
+
+    let request = TestRequest::default().header(header::ACCEPT_LANGUAGE, "").to_http_request();
+    assert_eq!(Locale::from_headers(request.headers()), Locale::Pl);
+
+    let request = TestRequest::default().header(header::ACCEPT_LANGUAGE, "de").to_http_request();
+    assert_eq!(Locale::from_headers(request.headers()), Locale::Pl);
+  }
+
+  #[test]
+  fn from_headers_works() {
+    let request = TestRequest::default().header(header::ACCEPT_LANGUAGE, "en").to_http_request();
+    assert_eq!(Locale::from_headers(request.headers()), Locale::En);
+  }
+}
@@ -19,52 +19,10 @@ macro_rules! http_201 {
 }
 
 #[macro_export]
-macro_rules! http_400 {
-  ($body:expr) => {
-    HttpResponse::BadRequest().json($body)
-  };
-}
-
-#[macro_export]
-macro_rules! http_401 {
-  () => {{
-    use actix_web::HttpResponse;
-    use crate::utils::responses::ErrorResponse;
-
-    HttpResponse::Unauthorized().json(ErrorResponse {
-      errors: vec!["Unauthorized"],
-    })
-  }};
-}
-
-#[macro_export]
-macro_rules! http_404 {
-  () => {{
-    use actix_web::HttpResponse;
-    use crate::utils::responses::ErrorResponse;
-
-    HttpResponse::NotFound().json(ErrorResponse {
-      errors: vec!["Not found"],
-    })
-  }};
-}
-
-#[macro_export]
-macro_rules! http_500 {
-  () => {{
-    use actix_web::HttpResponse;
-    use crate::utils::responses::ErrorResponse;
-
-    HttpResponse::InternalServerError().json(ErrorResponse {
-      errors: vec!["Unexpected error has occurred"],
-    })
-  }};
-}
-
-#[macro_export]
-macro_rules! require_refresh_token {
+macro_rules! require_bearer_token {
   ($req:ident) => {{
     use actix_web::http::header;
+    use crate::utils::errors::ApiError;
     // Check if header is present
     match $req.headers().get(header::AUTHORIZATION) {
       // Check if header value is a valid string
@@ -74,33 +32,78 @@ macro_rules! require_refresh_token {
           if let Some(value) = value.split("Bearer ").nth(1) {
             // Check if the string is not empty
             if value.trim().is_empty() {
-              return http_401!();
+              return Err(ApiError::Unauthorized);
             } else {
               value.trim().to_string()
             }
           } else {
-            return http_401!();
+            return Err(ApiError::Unauthorized);
           }
         }
-        Err(_) => return http_401!(),
+        Err(_) => return Err(ApiError::Unauthorized),
       },
-      None => return http_401!(),
+      None => return Err(ApiError::Unauthorized),
     }
   }};
 }
 
+#[macro_export]
+macro_rules! require_refresh_token {
+  ($req:ident) => {
+    $crate::require_bearer_token!($req)
+  };
+}
+
+#[macro_export]
+macro_rules! require_access_token {
+  ($req:ident) => {
+    $crate::require_bearer_token!($req)
+  };
+}
+
 #[macro_export]
 macro_rules! db_connect {
   ($db:expr) => {
     match $db.get() {
       Ok(connection) => connection,
       Err(error) => {
-        use actix_web::HttpResponse;
         use app::prelude::report_unexpected_err;
+        use crate::utils::errors::ApiError;
 
         report_unexpected_err!(error);
-        return HttpResponse::InternalServerError().body("Unexpected error has occurred");
+        return Err(ApiError::UnexpectedError);
       }
-    };
+    }
+  };
+}
+
+#[macro_export]
+macro_rules! async_db_connect {
+  ($db:expr) => {
+    match $db.get().await {
+      Ok(connection) => connection,
+      Err(error) => {
+        use app::prelude::report_unexpected_err;
+        use crate::utils::errors::ApiError;
+
+        report_unexpected_err!(error);
+        return Err(ApiError::UnexpectedError);
+      }
+    }
+  };
+}
+
+// The application shared by `main` and the tests. It's a macro, because the type of an app
+// wrapped in the logger can't be named.
+#[macro_export]
+macro_rules! build_app {
+  ($settings:expr, $db_pool:expr, $db_pool_metrics:expr, $async_db_pool:expr) => {
+    actix_web::App::new()
+      .data($settings)
+      .data($db_pool)
+      .data($db_pool_metrics)
+      .data($async_db_pool)
+      .configure(crate::config::routes::config)
+      .wrap(actix_web::middleware::Logger::default())
   };
 }
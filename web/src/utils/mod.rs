@@ -1,2 +1,9 @@
 mod macros;
+pub mod errors;
+pub mod locale;
+pub mod openapi;
 pub mod responses;
+pub mod router;
+
+#[cfg(test)]
+pub mod test;
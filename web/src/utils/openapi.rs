@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+
+use schemars::{gen::{SchemaGenerator, SchemaSettings}, JsonSchema};
+use serde_json::{json, Map, Value};
+
+use crate::utils::responses::{EmptyResponse, ErrorResponse, FieldErrorResponse};
+
+// Collects the operations described by the controllers, together with the JSON schemas of
+// their params and serializers, into an OpenAPI 3 document
+pub struct ApiSpec {
+  generator: SchemaGenerator,
+  paths: BTreeMap<String, Map<String, Value>>,
+}
+
+pub struct Operation<'a> {
+  generator: &'a mut SchemaGenerator,
+  operation: &'a mut Value,
+}
+
+impl ApiSpec {
+  pub fn new() -> Self {
+    Self {
+      generator: SchemaSettings::openapi3().into_generator(),
+      paths: BTreeMap::new(),
+    }
+  }
+
+  // Paths are relative to "/api/v1", the same way they're registered in the controllers
+  pub fn operation(&mut self, method: &str, path: &str, summary: &str) -> Operation<'_> {
+    let operation = self.paths.entry(path.to_string())
+      .or_default()
+      .entry(method.to_lowercase())
+      .or_insert(json!({
+        "summary": summary,
+        "parameters": [],
+        "responses": {},
+      }));
+
+    Operation { generator: &mut self.generator, operation }
+      .error(500, "Unexpected error has occurred")
+  }
+
+  #[cfg(test)]
+  pub fn operations(&self) -> Vec<(String, String)> {
+    self.paths.iter()
+      .flat_map(|(path, methods)| {
+        methods.keys().map(move |method| (method.to_uppercase(), path.clone()))
+      })
+      .collect()
+  }
+
+  pub fn into_json(mut self) -> Value {
+    json!({
+      "openapi": "3.0.3",
+      "info": {
+        "title": "mapy.nageografie.pl",
+        "version": env!("CARGO_PKG_VERSION"),
+      },
+      "servers": [{ "url": "/api/v1" }],
+      "paths": self.paths,
+      "components": {
+        "schemas": self.generator.take_definitions(),
+        "securitySchemes": {
+          "bearerAuth": { "type": "http", "scheme": "bearer" },
+        },
+      },
+    })
+  }
+}
+
+impl Default for ApiSpec {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<'a> Operation<'a> {
+  pub fn path_param(self, name: &str, description: &str) -> Self {
+    self.param(json!({
+      "name": name,
+      "in": "path",
+      "description": description,
+      "required": true,
+      "schema": { "type": "string" },
+    }))
+  }
+
+  pub fn query_param(self, name: &str, description: &str) -> Self {
+    self.param(json!({
+      "name": name,
+      "in": "query",
+      "description": description,
+      "required": false,
+      "schema": { "type": "string" },
+    }))
+  }
+
+  // Both the access and the refresh tokens are sent as "Authorization: Bearer <token>"
+  pub fn bearer_auth(self) -> Self {
+    self.operation["security"] = json!([{ "bearerAuth": [] }]);
+    self.error(401, "Missing, invalid or expired token")
+  }
+
+  pub fn body<T: JsonSchema>(self) -> Self {
+    let schema = self.generator.subschema_for::<T>();
+    self.operation["requestBody"] = json!({
+      "required": true,
+      "content": { "application/json": { "schema": schema } },
+    });
+    self.error(400, "Invalid params")
+  }
+
+  pub fn response<T: JsonSchema>(self, status: u16, description: &str) -> Self {
+    let schema = self.generator.subschema_for::<T>();
+    self.operation["responses"][status.to_string()] = json!({
+      "description": description,
+      "content": { "application/json": { "schema": schema } },
+    });
+    self
+  }
+
+  pub fn text_response(self, status: u16, description: &str) -> Self {
+    self.raw_response(status, "text/plain", description)
+  }
+
+  pub fn html_response(self, status: u16, description: &str) -> Self {
+    self.raw_response(status, "text/html", description)
+  }
+
+  // Static files, the content type is left out as it depends on the file
+  pub fn file_response(self, status: u16, description: &str) -> Self {
+    self.raw_response(status, "*/*", description)
+  }
+
+  pub fn empty_response(self, status: u16, description: &str) -> Self {
+    self.response::<EmptyResponse>(status, description)
+  }
+
+  pub fn error(self, status: u16, description: &str) -> Self {
+    self.response::<ErrorResponse<FieldErrorResponse>>(status, description)
+  }
+
+  fn raw_response(self, status: u16, content_type: &str, description: &str) -> Self {
+    self.operation["responses"][status.to_string()] = json!({
+      "description": description,
+      "content": { content_type: { "schema": { "type": "string" } } },
+    });
+    self
+  }
+
+  fn param(self, param: Value) -> Self {
+    if let Some(params) = self.operation["parameters"].as_array_mut() {
+      params.push(param);
+    }
+    self
+  }
+}
@@ -1,9 +1,19 @@
+use schemars::JsonSchema;
 use serde::Serialize;
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
+#[schemars(rename = "ErrorResponse")]
 pub struct ErrorResponse<T: Serialize> {
+  pub code: &'static str,
   pub errors: Vec<T>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema, Clone, PartialEq, Debug)]
+pub struct FieldErrorResponse {
+  pub field: String,
+  pub code: String,
+  pub message: String,
+}
+
+#[derive(Serialize, JsonSchema)]
 pub struct EmptyResponse {}
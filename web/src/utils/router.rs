@@ -0,0 +1,40 @@
+use std::future::Future;
+
+use actix_web::{dev::Factory, http::Method, web, FromRequest, Responder};
+
+// Registers the routes with actix and lists them in the route table at the same time, actix
+// doesn't expose the routes it has registered. The paths in the table are relative to the
+// root of the router, e.g. ("GET", "/countries/{code}") under "/api/v1".
+pub struct Router<'a> {
+  cfg: &'a mut web::ServiceConfig,
+  prefix: String,
+  table: &'a mut Vec<(Method, String)>,
+}
+
+impl<'a> Router<'a> {
+  pub fn new(cfg: &'a mut web::ServiceConfig, table: &'a mut Vec<(Method, String)>) -> Self {
+    Self { cfg, prefix: String::new(), table }
+  }
+
+  pub fn scope<F>(&mut self, path: &str, f: F) -> &mut Self
+  where
+    F: FnOnce(&mut Router),
+  {
+    let prefix = format!("{}{}", self.prefix, path);
+    let table = &mut *self.table;
+    self.cfg.service(web::scope(path).configure(|cfg| f(&mut Router { cfg, prefix, table })));
+    self
+  }
+
+  pub fn route<F, T, R, U>(&mut self, path: &str, method: Method, handler: F) -> &mut Self
+  where
+    F: Factory<T, R, U>,
+    T: FromRequest + 'static,
+    R: Future<Output = U> + 'static,
+    U: Responder + 'static,
+  {
+    self.table.push((method.clone(), format!("{}{}", self.prefix, path)));
+    self.cfg.route(path, web::method(method).to(handler));
+    self
+  }
+}
@@ -0,0 +1,221 @@
+use std::time::Duration;
+
+use actix_http::Request;
+use actix_web::{
+  dev::{MessageBody, Service, ServiceResponse},
+  http::{header, HeaderMap, StatusCode},
+  test::{self, TestRequest},
+  Error,
+};
+use serde_json::{json, Value};
+
+use db::models::SessionLifetimes;
+use db::prelude::{
+  create_async_database_connection_pool,
+  create_database_connection_pool,
+  DbConnection,
+  PoolMetrics,
+  PoolSettings,
+};
+use db::utils::test::TestSchema;
+
+use crate::build_app;
+use crate::config::settings::Settings;
+
+pub const PASSWORD: &str = "password";
+// Nothing listens on the port, so the connections are refused right away
+const UNREACHABLE_DATABASE_URL: &str = "postgres://postgres@127.0.0.1:1/mapy";
+
+// The application the way `main` builds it, backed by a test schema which is dropped
+// together with the app
+pub struct TestApp<S> {
+  service: S,
+  schema: TestSchema,
+}
+
+pub struct TestResponse {
+  pub status: StatusCode,
+  pub headers: HeaderMap,
+  // Bodies which aren't JSON are returned as strings
+  pub body: Value,
+}
+
+impl TestResponse {
+  // Fields and codes of the reported errors, e.g. `("email", "blank")`
+  pub fn errors(&self) -> Vec<(&str, &str)> {
+    self.body["errors"].as_array()
+      .map(|errors| {
+        errors.iter()
+          .map(|error| (error["field"].as_str().unwrap(), error["code"].as_str().unwrap()))
+          .collect()
+      })
+      .unwrap_or_default()
+  }
+}
+
+pub async fn test_app() -> TestApp<
+  impl Service<
+    Request = Request,
+    Response = ServiceResponse<impl MessageBody + Unpin>,
+    Error = Error,
+  >
+> {
+  let schema = TestSchema::new();
+  let database_url = schema.database_url();
+
+  init_test_app(schema, database_url, TestSchema::pool_settings()).await
+}
+
+// The app whose database can't be reached, e.g. for checking how it reports the failures.
+// The pool doesn't keep idle connections, so that it can be created without connecting.
+pub async fn unreachable_test_app() -> TestApp<
+  impl Service<
+    Request = Request,
+    Response = ServiceResponse<impl MessageBody + Unpin>,
+    Error = Error,
+  >
+> {
+  let pool_settings = PoolSettings {
+    min_idle: Some(0),
+    connection_timeout: Duration::from_millis(100),
+    ..TestSchema::pool_settings()
+  };
+
+  init_test_app(TestSchema::new(), UNREACHABLE_DATABASE_URL.to_string(), pool_settings).await
+}
+
+async fn init_test_app(
+  schema: TestSchema,
+  database_url: String,
+  pool_settings: PoolSettings,
+) -> TestApp<
+  impl Service<
+    Request = Request,
+    Response = ServiceResponse<impl MessageBody + Unpin>,
+    Error = Error,
+  >
+> {
+  let db_pool_metrics = PoolMetrics::default();
+  let db_pool = create_database_connection_pool(&database_url, &pool_settings, &db_pool_metrics)
+    .unwrap();
+  let async_db_pool = create_async_database_connection_pool(&database_url, &pool_settings)
+    .unwrap();
+  let settings = Settings {
+    port: 0,
+    database_url,
+    database_pool: pool_settings,
+    rollbar: None,
+    sessions: SessionLifetimes::default(),
+  };
+  let service = test::init_service(build_app!(
+    settings,
+    db_pool,
+    db_pool_metrics,
+    async_db_pool
+  )).await;
+
+  TestApp { service, schema }
+}
+
+impl<S, B> TestApp<S>
+where
+  S: Service<Request = Request, Response = ServiceResponse<B>, Error = Error>,
+  B: MessageBody + Unpin,
+{
+  // For setting up the records which can't be created through the API
+  pub fn db(&self) -> DbConnection {
+    self.schema.connection()
+  }
+
+  pub async fn call(&mut self, request: TestRequest) -> TestResponse {
+    let response = test::call_service(&mut self.service, request.to_request()).await;
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = test::read_body(response).await;
+
+    TestResponse {
+      status,
+      headers,
+      body: serde_json::from_slice(&body)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&body).into_owned())),
+    }
+  }
+
+  pub async fn sign_up(&mut self, email: &str) {
+    let response = self.call(
+      TestRequest::post()
+        .uri("/api/v1/teachers")
+        .set_json(&json!({ "email": email, "password": PASSWORD }))
+    ).await;
+
+    assert_eq!(response.status, StatusCode::CREATED);
+  }
+
+  // Returns the serialized session
+  pub async fn sign_in(&mut self, email: &str) -> Value {
+    let response = self.call(
+      TestRequest::post()
+        .uri("/api/v1/teachers/sessions")
+        .set_json(&json!({ "email": email, "password": PASSWORD }))
+    ).await;
+
+    assert_eq!(response.status, StatusCode::CREATED);
+    response.body
+  }
+
+  pub async fn sign_up_and_in(&mut self, email: &str) -> Value {
+    self.sign_up(email).await;
+    self.sign_in(email).await
+  }
+
+  // Returns the serialized organization, with the session's owner as its admin
+  pub async fn create_organization(&mut self, session: &Value) -> Value {
+    let response = self.call_as(
+      session,
+      TestRequest::post()
+        .uri("/api/v1/organizations")
+        .set_json(&json!({ "name": "SP 1 Warszawa" })),
+    ).await;
+
+    assert_eq!(response.status, StatusCode::CREATED);
+    response.body
+  }
+
+  // Returns the serialized invitation
+  pub async fn invite(&mut self, session: &Value, organization: &Value, email: &str) -> Value {
+    let uri = format!(
+      "/api/v1/organizations/{}/invitations",
+      organization["uuid"].as_str().unwrap(),
+    );
+    let response = self.call_as(
+      session,
+      TestRequest::post().uri(&uri).set_json(&json!({ "email": email })),
+    ).await;
+
+    assert_eq!(response.status, StatusCode::CREATED);
+    response.body
+  }
+
+  // Signs up a new member of the organization and returns their session
+  pub async fn add_member(&mut self, session: &Value, organization: &Value, email: &str) -> Value {
+    let invitation = self.invite(session, organization, email).await;
+    let member_session = self.sign_up_and_in(email).await;
+    let uri = format!(
+      "/api/v1/organizations/invitations/{}/accept",
+      invitation["token"].as_str().unwrap(),
+    );
+    let response = self.call_as(&member_session, TestRequest::post().uri(&uri)).await;
+
+    assert_eq!(response.status, StatusCode::OK);
+    member_session
+  }
+
+  // Makes the request on behalf of the session's owner
+  pub async fn call_as(&mut self, session: &Value, request: TestRequest) -> TestResponse {
+    self.call_with_token(session["access_token"].as_str().unwrap(), request).await
+  }
+
+  pub async fn call_with_token(&mut self, token: &str, request: TestRequest) -> TestResponse {
+    self.call(request.header(header::AUTHORIZATION, format!("Bearer {}", token))).await
+  }
+}